@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use std::time::Duration;
+use theattyr::{
+    animation::{Animation, Animations},
+    pacing::Pacing,
+};
+
+/// Largest embedded files.
+const FILES: &[&str] = &["movglobe.vt", "fishy2.vt", "xmas.large", "xmas2.vt"];
+
+/// Interval between the frames.
+const INTERVAL: Duration = Duration::from_millis(16);
+
+/// Number of frames to measure at the start and at the end of a file.
+const FRAMES: usize = 100;
+
+fn load(name: &str) -> Vec<u8> {
+    Animations::get(name)
+        .expect("no animation found")
+        .data
+        .into_owned()
+}
+
+fn new_animation(data: &[u8]) -> Animation {
+    Animation::new(data.to_vec(), Pacing::Line(INTERVAL), 24, 80)
+}
+
+fn render_frames(animation: &mut Animation, buffer: &mut Buffer, frames: usize) {
+    for _ in 0..frames {
+        animation.advance(INTERVAL);
+        animation.render(buffer.area, buffer);
+    }
+}
+
+fn bench_playback(c: &mut Criterion) {
+    let area = Rect::new(0, 0, 80, 24);
+    let mut group = c.benchmark_group("playback");
+    for name in FILES {
+        let data = load(name);
+        let lines = data.iter().filter(|b| **b == b'\n').count();
+        group.bench_function(format!("{name}/full"), |b| {
+            b.iter_batched(
+                || (new_animation(&data), Buffer::empty(area)),
+                |(mut animation, mut buffer)| {
+                    while !animation.is_rendered {
+                        render_frames(&mut animation, &mut buffer, 1);
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        // per-frame cost should not depend on how far into the file we are
+        group.bench_function(format!("{name}/head"), |b| {
+            b.iter_batched(
+                || (new_animation(&data), Buffer::empty(area)),
+                |(mut animation, mut buffer)| render_frames(&mut animation, &mut buffer, FRAMES),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(format!("{name}/tail"), |b| {
+            b.iter_batched(
+                || {
+                    let mut animation = new_animation(&data);
+                    let mut buffer = Buffer::empty(area);
+                    render_frames(&mut animation, &mut buffer, lines.saturating_sub(FRAMES));
+                    (animation, buffer)
+                },
+                |(mut animation, mut buffer)| render_frames(&mut animation, &mut buffer, FRAMES),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_playback);
+criterion_main!(benches);
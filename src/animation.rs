@@ -1,37 +1,482 @@
-use std::{
-    collections::HashMap,
-    io::{BufRead, BufReader, Cursor},
-    sync::OnceLock,
-};
+use std::time::{Duration, Instant};
 
 use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 use rust_embed::Embed;
 use tui_term::widget::PseudoTerminal;
-use vt100::Parser;
+use vt100::{Parser, Screen};
+
+use crate::{
+    format::{sauce::Sauce, Recording},
+    pacing::{Pacing, Timeline},
+    stream::Stream,
+};
+
+/// Minimum playback speed.
+pub const MIN_SPEED: f64 = 0.25;
+
+/// Maximum playback speed.
+pub const MAX_SPEED: f64 = 16.;
+
+/// Minimum number of bytes between two keyframes.
+const KEYFRAME_INTERVAL: usize = 16 * 1024;
+
+/// Maximum number of bytes to look back for an unfinished escape sequence.
+const MAX_SEQUENCE_LEN: usize = 64;
 
 #[derive(Embed)]
 #[folder = "vt100"]
 pub struct Animations;
 
+/// Unit of a single playback step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Until the end of the line.
+    Line,
+    /// A single byte.
+    Byte,
+    /// An escape sequence or a single character.
+    Sequence,
+}
+
+/// Escape character.
+const ESC: u8 = 0x1b;
+
+/// Cursor state saved via DECSC (`ESC 7`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SavedCursor {
+    /// Cursor position as row and column.
+    pub position: (u16, u16),
+    /// Escape codes that set the text attributes.
+    pub attributes: Vec<u8>,
+    /// Is the origin mode set?
+    pub origin_mode: bool,
+}
+
+/// Terminal modes that are not reproduced by [`Screen::state_formatted`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Modes {
+    /// Parameters of the scroll region (DECSTBM) as top and bottom rows.
+    pub scroll_region: Option<(u16, u16)>,
+    /// Is the origin mode (DECOM) set?
+    pub origin_mode: bool,
+    /// Saved cursor.
+    pub saved_cursor: Option<SavedCursor>,
+}
+
+impl Modes {
+    /// Updates the modes for the given escape sequence.
+    ///
+    /// The cursor is saved by the caller since it depends on the screen.
+    fn apply(&mut self, sequence: &[u8]) {
+        match sequence {
+            [ESC, b'c'] => *self = Self::default(),
+            [ESC, b'8'] => {
+                self.origin_mode = self
+                    .saved_cursor
+                    .as_ref()
+                    .is_some_and(|cursor| cursor.origin_mode);
+            }
+            [ESC, b'[', b'?', params @ .., c @ (b'h' | b'l')]
+                if params.split(|b| *b == b';').any(|param| param == b"6") =>
+            {
+                self.origin_mode = *c == b'h';
+            }
+            [ESC, b'[', params @ .., b'r'] if params.iter().all(|b| b"0123456789;".contains(b)) => {
+                let mut params = params.split(|b| *b == b';').map(|param| {
+                    std::str::from_utf8(param)
+                        .ok()
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or_default()
+                });
+                self.scroll_region = Some((
+                    params.next().unwrap_or_default(),
+                    params.next().unwrap_or_default(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the escape codes that reproduce the given screen with the modes.
+    fn state_formatted(&self, screen: &Screen) -> Vec<u8> {
+        let mut state = Vec::new();
+        if let Some(cursor) = &self.saved_cursor {
+            // the scroll region is the full screen yet, i.e. the position is absolute
+            if cursor.origin_mode {
+                state.extend_from_slice(b"\x1b[?6h");
+            }
+            let (row, col) = cursor.position;
+            state.extend_from_slice(format!("\x1b[{};{}H", row + 1, col + 1).as_bytes());
+            state.extend_from_slice(&cursor.attributes);
+            state.extend_from_slice(b"\x1b7\x1b[?6l");
+        }
+        state.extend_from_slice(&screen.state_formatted());
+        if self.scroll_region.is_none() && !self.origin_mode {
+            return state;
+        }
+        // the cursor is moved to the home position, so it is restored afterwards
+        let mut top = 0;
+        if let Some((first, last)) = self.scroll_region {
+            state.extend_from_slice(format!("\x1b[{first};{last}r").as_bytes());
+            let rows = screen.size().0;
+            let first = first.max(1) - 1;
+            let last = if last == 0 { rows } else { last.min(rows) }.saturating_sub(1);
+            if first < last {
+                top = first;
+            }
+        }
+        let (mut row, col) = screen.cursor_position();
+        if self.origin_mode {
+            state.extend_from_slice(b"\x1b[?6h");
+            row = row.saturating_sub(top);
+        }
+        state.extend_from_slice(format!("\x1b[{};{}H", row + 1, col + 1).as_bytes());
+        state
+    }
+}
+
+/// Snapshot of the terminal state at a certain offset.
+#[derive(Clone, Debug)]
+pub struct Keyframe {
+    /// Offset in the data.
+    pub offset: usize,
+    /// Escape codes that reproduce the terminal state.
+    pub state: Vec<u8>,
+    /// Terminal modes at the offset.
+    pub modes: Modes,
+}
+
 pub struct Animation {
     /// Is the animation rendered?
     pub is_rendered: bool,
-    // Reader for the file.
-    pub reader: BufReader<Cursor<Vec<u8>>>,
+    /// Is the playback paused?
+    pub is_paused: bool,
+    /// Contents of the file.
+    pub data: Vec<u8>,
+    /// Stream that the data is received from.
+    ///
+    /// The animation is not rendered until the end of the stream is reached.
+    pub stream: Option<Stream>,
+    /// Number of bytes fed to the parser.
+    pub position: usize,
+    /// Number of bytes scanned for the terminal modes.
+    ///
+    /// It is behind the position if an escape sequence is not fed completely.
+    pub scanned: usize,
+    /// Terminal modes that are tracked for the keyframes.
+    pub modes: Modes,
+    /// Fixed terminal size as rows and columns.
+    ///
+    /// The parser is not resized to the widget area if it is set.
+    pub size: Option<(u16, u16)>,
+    /// Fixed terminal width in columns.
+    ///
+    /// Only the rows follow the widget area if it is set.
+    pub width: Option<u16>,
+    /// SAUCE metadata.
+    pub sauce: Option<Sauce>,
     /// VT100 parser.
+    ///
+    /// Only the newly released bytes are fed to it,
+    /// the screen state is kept in between.
     pub parser: Parser,
-    /// Buffer.
-    pub buffer: String,
+    /// Playback pacing.
+    pub pacing: Pacing,
+    /// Timeline of the playback.
+    pub timeline: Timeline,
+    /// Playback clock.
+    pub clock: Duration,
+    /// Playback speed multiplier.
+    pub speed: f64,
+    /// Last time the playback clock was updated.
+    pub last_update: Instant,
+    /// Keyframes recorded during the playback, sorted by offset.
+    pub keyframes: Vec<Keyframe>,
 }
 
 impl Default for Animation {
     fn default() -> Self {
         Self {
             is_rendered: true,
-            reader: BufReader::new(Cursor::new(Vec::new())),
+            is_paused: false,
+            data: Vec::new(),
+            stream: None,
+            position: 0,
+            scanned: 0,
+            modes: Modes::default(),
+            size: None,
+            width: None,
+            sauce: None,
             parser: Parser::default(),
-            buffer: String::new(),
+            pacing: Pacing::Line(Duration::ZERO),
+            timeline: Timeline::Frames(Vec::new()),
+            clock: Duration::ZERO,
+            speed: 1.,
+            last_update: Instant::now(),
+            keyframes: Vec::new(),
+        }
+    }
+}
+
+impl Animation {
+    /// Constructs a new instance of [`Animation`] for the given terminal size.
+    pub fn new(data: Vec<u8>, pacing: Pacing, rows: u16, cols: u16) -> Self {
+        Self {
+            is_rendered: data.is_empty(),
+            timeline: Timeline::new(pacing, &data),
+            pacing,
+            data,
+            parser: Parser::new(rows, cols, 0),
+            ..Self::default()
+        }
+    }
+
+    /// Constructs a new instance of [`Animation`] that plays the given recording.
+    ///
+    /// The recorded timing and terminal size (or width) are preferred over the given ones.
+    pub fn from_recording(recording: Recording, pacing: Pacing, rows: u16, cols: u16) -> Self {
+        let (size, width) = (recording.size, recording.width());
+        let (rows, cols) = size.unwrap_or((rows, width.unwrap_or(cols)));
+        let mut animation = Self::new(recording.data, pacing, rows, cols);
+        if let Some(frames) = recording.frames {
+            animation.timeline = Timeline::Frames(frames);
+        }
+        animation.size = size;
+        animation.width = width;
+        animation.sauce = recording.sauce;
+        animation
+    }
+
+    /// Constructs a new instance of [`Animation`] that plays the given stream.
+    pub fn from_stream(stream: Stream, pacing: Pacing, rows: u16, cols: u16) -> Self {
+        Self {
+            is_rendered: false,
+            stream: Some(stream),
+            ..Self::new(Vec::new(), pacing, rows, cols)
+        }
+    }
+
+    /// Advances the playback clock by the time elapsed since the last update.
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        self.advance(now - self.last_update);
+        self.last_update = now;
+    }
+
+    /// Advances the playback clock by the given duration
+    /// and feeds the released bytes to the parser.
+    pub fn advance(&mut self, elapsed: Duration) {
+        self.receive();
+        if self.is_rendered || self.is_paused {
+            return;
+        }
+        self.clock += elapsed.mul_f64(self.speed);
+        let offset = self.timeline.offset_at(self.clock);
+        self.feed(offset);
+    }
+
+    /// Resizes the terminal unless the animation has a fixed size.
+    ///
+    /// Only the rows are resized if the animation has a fixed width.
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        if self.size.is_none() {
+            self.parser.set_size(rows, self.width.unwrap_or(cols));
+        }
+    }
+
+    /// Receives the new data from the stream.
+    fn receive(&mut self) {
+        let Some(stream) = &self.stream else {
+            return;
+        };
+        let len = self.data.len();
+        let is_finished = stream.receive(&mut self.data);
+        if self.data.len() > len {
+            self.timeline.extend(self.pacing, &self.data);
+        }
+        if is_finished {
+            self.stream = None;
+        }
+    }
+
+    /// Returns the ratio (between 0 and 1) of the data fed to the parser.
+    pub fn progress(&self) -> f64 {
+        if self.data.is_empty() {
+            0.
+        } else {
+            self.position as f64 / self.data.len() as f64
+        }
+    }
+
+    /// Returns the estimated duration of the playback.
+    pub fn duration(&self) -> Duration {
+        self.timeline.time_at(self.data.len())
+    }
+
+    /// Sets the playback speed multiplier.
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    /// Pauses or resumes the playback.
+    pub fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    /// Pauses the playback and feeds the next chunk of data to the parser.
+    pub fn step(&mut self, step: Step) {
+        self.is_paused = true;
+        let rest = &self.data[self.position..];
+        let len = match step {
+            Step::Line => rest
+                .iter()
+                .position(|b| *b == b'\n')
+                .map(|i| i + 1)
+                .unwrap_or(rest.len()),
+            Step::Byte => 1,
+            Step::Sequence => sequence_len(rest),
+        };
+        self.feed(self.position + len);
+        self.clock = self.timeline.time_at(self.position);
+    }
+
+    /// Seeks to the given time.
+    pub fn seek_time(&mut self, time: Duration) {
+        self.seek(self.timeline.offset_at(time));
+        self.clock = time.min(self.timeline.time_at(self.data.len()));
+    }
+
+    /// Seeks to the given ratio (between 0 and 1) of the data.
+    pub fn seek_ratio(&mut self, ratio: f64) {
+        self.seek((self.data.len() as f64 * ratio.clamp(0., 1.)) as usize);
+    }
+
+    /// Seeks to the given offset in the data.
+    ///
+    /// The terminal state is restored from the nearest keyframe
+    /// and the rest of the data is replayed until the offset.
+    pub fn seek(&mut self, offset: usize) {
+        let offset = offset.min(self.data.len());
+        let index = self.keyframes.partition_point(|k| k.offset <= offset);
+        let keyframe = index.checked_sub(1).map(|i| &self.keyframes[i]);
+        let start = keyframe.map(|k| k.offset).unwrap_or_default();
+        if offset < self.position || start > self.position {
+            let (rows, cols) = self.parser.screen().size();
+            self.parser = Parser::new(rows, cols, 0);
+            if let Some(keyframe) = keyframe {
+                self.parser.process(&keyframe.state);
+            }
+            self.modes = keyframe.map(|k| k.modes.clone()).unwrap_or_default();
+            self.position = start;
+            self.scanned = start;
+        }
+        self.feed(offset);
+        self.clock = self.timeline.time_at(self.position);
+    }
+
+    /// Feeds the data up to the given offset to the parser.
+    ///
+    /// Keyframes are recorded along the way.
+    fn feed(&mut self, offset: usize) {
+        let offset = offset.min(self.data.len());
+        while self.position < offset {
+            let end = offset.min(self.position + KEYFRAME_INTERVAL);
+            self.process(end);
+            let last = self.keyframes.last().map(|k| k.offset).unwrap_or_default();
+            if self.position >= last + KEYFRAME_INTERVAL
+                && self.scanned == self.position
+                && is_boundary(&self.data, self.position)
+            {
+                self.keyframes.push(Keyframe {
+                    offset: self.position,
+                    state: self.modes.state_formatted(self.parser.screen()),
+                    modes: self.modes.clone(),
+                });
+            }
         }
+        self.is_rendered = self.position == self.data.len() && self.stream.is_none();
+    }
+
+    /// Feeds the data up to the given offset to the parser and tracks the terminal modes.
+    ///
+    /// The data is fed up to each DECSC (`ESC 7`) to save the cursor state.
+    fn process(&mut self, end: usize) {
+        loop {
+            let Some(start) = self.data[self.scanned..end]
+                .iter()
+                .position(|b| *b == ESC)
+                .map(|i| self.scanned + i)
+            else {
+                self.scanned = end;
+                break;
+            };
+            let sequence_end = start + sequence_len(&self.data[start..]);
+            if sequence_end > end || (sequence_end == self.data.len() && self.stream.is_some()) {
+                self.scanned = start;
+                break;
+            }
+            let sequence = &self.data[start..sequence_end];
+            if sequence == [ESC, b'7'] {
+                if self.position < sequence_end {
+                    self.parser.process(&self.data[self.position..sequence_end]);
+                    self.position = sequence_end;
+                }
+                let screen = self.parser.screen();
+                self.modes.saved_cursor = Some(SavedCursor {
+                    position: screen.cursor_position(),
+                    attributes: screen.attributes_formatted(),
+                    origin_mode: self.modes.origin_mode,
+                });
+            } else {
+                self.modes.apply(sequence);
+            }
+            self.scanned = sequence_end;
+        }
+        if self.position < end {
+            self.parser.process(&self.data[self.position..end]);
+            self.position = end;
+        }
+    }
+}
+
+/// Returns whether the given offset is not in the middle of an escape sequence or a character.
+fn is_boundary(data: &[u8], offset: usize) -> bool {
+    if data.get(offset).is_some_and(|b| (0x80..0xc0).contains(b)) {
+        return false;
+    }
+    let start = offset.saturating_sub(MAX_SEQUENCE_LEN);
+    match data[start..offset].iter().rposition(|b| *b == 0x1b) {
+        Some(i) => start + i + sequence_len(&data[start + i..]) <= offset,
+        None => true,
+    }
+}
+
+/// Returns the length of the escape sequence or the character at the start of the data.
+fn sequence_len(data: &[u8]) -> usize {
+    const BEL: u8 = 0x07;
+    match data {
+        [] => 0,
+        [ESC, b'[', rest @ ..] => rest
+            .iter()
+            .position(|b| (0x40..=0x7e).contains(b))
+            .map(|i| i + 3)
+            .unwrap_or(data.len()),
+        [ESC, b']' | b'P' | b'X' | b'^' | b'_', rest @ ..] => (0..rest.len())
+            .find_map(|i| match rest[i..] {
+                [BEL, ..] => Some(i + 3),
+                [ESC, b'\\', ..] => Some(i + 4),
+                _ => None,
+            })
+            .unwrap_or(data.len()),
+        [ESC, rest @ ..] => rest
+            .iter()
+            .position(|b| !(0x20..=0x2f).contains(b))
+            .map(|i| i + 2)
+            .unwrap_or(data.len()),
+        [first, ..] => match first.leading_ones() {
+            2..=4 => (first.leading_ones() as usize).min(data.len()),
+            _ => 1,
+        },
     }
 }
 
@@ -40,137 +485,86 @@ impl Widget for &mut Animation {
     where
         Self: Sized,
     {
-        let mut line_buffer = String::new();
-        if let Ok(bytes_read) = self.reader.read_line(&mut line_buffer) {
-            self.buffer += &line_buffer;
-            self.parser.process(self.buffer.as_bytes());
-            let pseudo_term = PseudoTerminal::new(self.parser.screen());
-            pseudo_term.render(area, buf);
-            if bytes_read == 0 {
-                self.is_rendered = true;
-            }
-        }
+        let pseudo_term = PseudoTerminal::new(self.parser.screen());
+        pseudo_term.render(area, buf);
     }
 }
 
-pub fn descriptions() -> &'static HashMap<&'static str, &'static str> {
-    static MEM: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
-    MEM.get_or_init(|| {
-        let mut m = HashMap::new();
-        m.insert("bambi.vt", "Bambi vs. Godzilla");
-        m.insert("bambi_godzila", "Bambi Versus Godzilla, from Dave Brett");
-        m.insert("barney.vt", "Barney Being Crushed by a Rock");
-        m.insert("beer.vt", "Time for a Beer Break, Folks!");
-        m.insert("bevis.butthead.vt", "Beavis and Butthead");
-        m.insert("blinkeyes.vt", "Blinking Eyes");
-        m.insert("bomb.vt", "The Bomb Test");
-        m.insert("bugsbunny.vt", "Bugs Bunny: That's All, Folks");
-        m.insert("cartwhee.vt", "Doing a Cartwheel");
-        m.insert("castle.vt", "Disney's Fantasy in the Sky, by Don Bertino");
-        m.insert("cert18.vt", "Make Money Fast: The Revenge, by GtB (1993)");
-        m.insert("cow.vt", "Exploding Cow, Hauled off by U-Mass Food Service");
-        m.insert("cowboom.vt", "Cow Explodes, Gets Hauled Off");
-        m.insert("crash.vt", "Shuttle Blows Up");
-        m.insert("cursor.vt", "Cursor Control Examples in VT100");
-        m.insert("delay.vt", "A Small Delay");
-        m.insert("demo.vt", "Alan's Impressive Demonstration");
-        m.insert("dirty.vt", "Someone Having an Awful Amount of Fun");
-        m.insert("dogs.vt", "Fucking Dogs");
-        m.insert(
-            "dont-wor.vt",
-            "George Custer's Last Stand: Don't Worry, be Happy",
-        );
-        m.insert(
-            "dontworry.vt",
-            "Man Being Shot with Arrows: Don't Worry, Be Happy",
-        );
-        m.insert("duckpaint.vt", "Duck Painting");
-        m.insert("firework.vt", "Fireworks by Chen Lin");
-        m.insert("fireworks.vt", "Guy Setting Off Fireworks");
-        m.insert("fishy-fishy.vt", "3-D Fishy Fishy");
-        m.insert("fishy.vt", "Fish Swiming By, Glug Glug");
-        m.insert("fishy2.vt", "Shamus the Fish by David Rybolt (1994)");
-        m.insert("flatmap.vt", "Shifting Flat World Map");
-        m.insert("frogs.vt", "Hopping Frog");
-        m.insert("glass.vt", "Filling Glass of Liquid");
-        m.insert("globe.vt", "ABSOLUTELY EXCELLENT Spinning Globe");
-        m.insert("hallow.vt", "Happy Halloween");
-        m.insert("hello.vt", "HELLO!");
-        m.insert("juanspla.vt", "Plan File in the Form of a Typewriter");
-        m.insert("july.4.vt", "July 4th Animation");
-        m.insert("jumble.vt", "Now Is the Time for All Good Men");
-        m.insert("maingate.vt", "The Disneyland Main Gate, by Don Bertino");
-        m.insert("mark_twain.vt", "The Mark Twain Ferry, by Don Bertino");
-        m.insert("monkey.vt", "The Monkey Gives You The Finger");
-        m.insert("monorail.vt", "Disneyland's Monorail, by Don Bertino");
-        m.insert("moon.animation", "Winking Moon Says Good Evening");
-        m.insert("movglobe.vt", "Incredible Spinning, Moving Globe");
-        m.insert("mr_pumpkin", "Happy Halloween Pumpkin by Mike Kamlet");
-        m.insert("nasa.vt", "NASA: Keep Reaching for the Stars, by A.J.L.");
-        m.insert("new_year.vt", "Happy New Year to You");
-        m.insert("newbeer.vt", "Working on a VT100");
-        m.insert("nifty.vt", "Small Animated Word NIFTY");
-        m.insert("outerlimits.vt", "The Outer Limits");
-        m.insert("pac3d.vt", "Pac Man in 3-D Chomping a Ghost");
-        m.insert("paradise.vt", "A Bomb in Paradise by Gonad the Barbarian");
-        m.insert("peace.vt", "Imagine World Peace by John G. Poupore");
-        m.insert("prey.vt", "Klingon Bird of Prey");
-        m.insert("prey_col.vt", "Klingon Bird of Prey");
-        m.insert("safesex.vt", "Safe Sex (Literally)");
-        m.insert("shuttle.vt", "Technology, Who Needs It");
-        m.insert("skyway.vt", "Disneyland's Skyway, by Don Bertino");
-        m.insert("snowing", "Merry Christmas from Woodrow");
-        m.insert("snowing.vt", "Tis the Season: Merry Christmas");
-        m.insert("spinweb.vt", "Spinning Web by R.L. Samuell (April 6, 1994)");
-        m.insert("sship.vt", "Space Ship Warps and Fires");
-        m.insert(
-            "startrek.vt",
-            "Star Trek Enterprise Blows up Politically Correct New Enterprise",
-        );
-        m.insert("strike.vt", "Bowling a Strike");
-        m.insert("sun.vt", "A Happy Sun");
-        m.insert("surf.vt", "Surfing Wave (In 3-D)");
-        m.insert("tetris.vt", "Tetris Game");
-        m.insert("tomorrw.vt", "Disneyland's Tomorrowland, by Don Bertino");
-        m.insert(
-            "torturet.vt",
-            "VT100 FONT: The VT-100 Torture Test by Joe Smith (May 8, 1985)",
-        );
-        m.insert("treadmill.vt", "The Treadmill, by GtB Productions (1993)");
-        m.insert("trek.vt", "The Enterprise Blows up an RCA Satellite");
-        m.insert("trekvid.vt", "Politically Incorrect Star Trek");
-        m.insert("turkey.vt", "Happy Thanksgiving");
-        m.insert("tv.vt", "The Outer Limits Television Show");
-        m.insert("twilight.vt", "The Twilight Zone");
-        m.insert("twilightzone.vt", "Twilight Zone Opener");
-        m.insert("valentin.vt", "Happy Valentine's Day, Beth and Dave");
-        m.insert("valentine.vt", "Happy Valentine's Day, Jane and Chris");
-        m.insert("van_halen.vt", "Van Halen's Song 5150, Animated");
-        m.insert("wineglas.vt", "Wine Glass Filling");
-        m.insert(
-            "xmas-00.vt",
-            "Santa Holds Moving Sign: Merry Christmas, Happy New Year",
-        );
-        m.insert("xmas-01.vt", "Merry Christmas");
-        m.insert("xmas-02.vt", "Bird Flies By, Tree Grows, Merry Christmas");
-        m.insert("xmas-03.vt", "Merry Christmas (Tree, Train, Presents)");
-        m.insert(
-            "xmas-04.vt",
-            "Merry Christmas, Champagne Glass Filling, Jack-in-the-Box",
-        );
-        m.insert(
-            "xmas-05.vt",
-            "Happy Holidays, Starry Night, Christmas Tree, by Peter",
-        );
-        m.insert("xmas-06.vt", "Merry Christmas: Hearth and Tree");
-        m.insert("xmas-07.vt", "A Christmas Card: Merry Christmas, from MIS");
-        m.insert("xmas-08.vt", "Christmas Eve, 1992 (1992)");
-        m.insert("xmas-09.vt", "Merry Christmas: Reindeer Land on Roof");
-        m.insert("xmas.large", "Compilation of Several Christmas Animations");
-        m.insert("xmas.vt", "Merry Christmas");
-        m.insert("xmas2.vt", "Large Collection of Christmas Animations");
-        m.insert("xmasshort.vt", "Merry Christmas, Tree, Train, Present");
-        m.insert("zorro.vt", "The Story of Zorro by Cian O'Kiersey");
-        m
-    })
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::encoding::{self, Encoding};
+
+    #[test]
+    fn test_sequence_len() {
+        assert_eq!(0, sequence_len(b""));
+        assert_eq!(1, sequence_len(b"ab"));
+        assert_eq!(2, sequence_len("éa".as_bytes()));
+        assert_eq!(5, sequence_len(b"\x1b[12Hab"));
+        assert_eq!(3, sequence_len(b"\x1b(Bab"));
+        assert_eq!(2, sequence_len(b"\x1b7ab"));
+        assert_eq!(8, sequence_len(b"\x1b]0;abc\x07de"));
+        assert_eq!(9, sequence_len(b"\x1b]0;abc\x1b\\de"));
+        assert_eq!(4, sequence_len(b"\x1b[12"));
+    }
+
+    #[test]
+    fn test_seek() {
+        let data = (0..10_000)
+            .map(|i| format!("\x1b[H\x1b[2J{i}\n"))
+            .collect::<String>()
+            .into_bytes();
+        let pacing = Pacing::Line(Duration::from_millis(10));
+        let mut animation = Animation::new(data.clone(), pacing, 24, 80);
+        animation.seek_ratio(1.);
+        assert!(animation.is_rendered);
+        assert!(!animation.keyframes.is_empty());
+        for time in [5000, 20, 12345, 99990, 0] {
+            let time = Duration::from_millis(time);
+            animation.seek_time(time);
+            let mut expected = Animation::new(data.clone(), pacing, 24, 80);
+            expected.advance(time);
+            assert_eq!(expected.position, animation.position);
+            assert_eq!(expected.clock, animation.clock);
+            assert_eq!(
+                expected.parser.screen().contents(),
+                animation.parser.screen().contents()
+            );
+        }
+        // the keyframes reproduce the scroll region, origin mode and saved cursor
+        for name in Animations::iter() {
+            let data = Animations::get(&name)
+                .expect("no animation")
+                .data
+                .into_owned();
+            let data = encoding::transcode(data.into(), Encoding::Auto).data;
+            let mut animation = Animation::new(data.clone(), pacing, 24, 80);
+            animation.seek_ratio(1.);
+            for ratio in [0.25, 0.5, 0.75, 0.9] {
+                animation.seek_ratio(ratio);
+                let mut expected = Animation::new(data.clone(), pacing, 24, 80);
+                expected.feed(animation.position);
+                assert_eq!(
+                    expected.parser.screen().contents_formatted(),
+                    animation.parser.screen().contents_formatted(),
+                    "{name} at {ratio}"
+                );
+                assert_eq!(
+                    expected.parser.screen().cursor_position(),
+                    animation.parser.screen().cursor_position(),
+                    "{name} at {ratio}"
+                );
+                let mut seeked = Animation::new(data.clone(), pacing, 24, 80);
+                seeked.keyframes = animation.keyframes.clone();
+                seeked.seek_ratio(ratio);
+                seeked.feed(data.len());
+                expected.feed(data.len());
+                assert_eq!(
+                    expected.parser.screen().contents_formatted(),
+                    seeked.parser.screen().contents_formatted(),
+                    "{name} from {ratio}"
+                );
+            }
+        }
+    }
 }
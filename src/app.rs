@@ -1,27 +1,33 @@
 use std::{
-    io::{BufReader, Cursor},
+    mem,
     time::{Duration, Instant},
 };
 use tachyonfx::{
     fx::{self},
     Duration as FxDuration, Effect, EffectRenderer, Interpolation, Shader,
 };
-use vt100::Parser;
 
 use crate::{
-    animation::{descriptions, Animation, Animations},
-    event::{handle_key_events, Event, EventHandler},
+    animation::Animation,
+    event::{handle_key_events, handle_mouse_events, Event, EventHandler},
+    format,
     fps::Fps,
+    history::History,
+    library::{self, Entry},
+    listing::{Item, Listing, SortMode, RECENT_LEN},
+    pacing::Pacing,
+    playlist::{PlayMode, Playlist},
+    search::Search,
     Args,
 };
 use color_eyre::Result;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Style, Stylize},
-    text::{Line, ToLine},
+    text::{Line, Span},
     widgets::{
         block::{Position, Title},
-        Block, List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Block, LineGauge, List, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState,
     },
     DefaultTerminal, Frame,
 };
@@ -38,11 +44,33 @@ pub struct App {
     /// List state.
     pub list_state: ListState,
     /// Animations.
-    pub animations: Vec<String>,
+    pub animations: Vec<Entry>,
+    /// Search in the animation list.
+    pub search: Search,
+    /// Animations that are listed, i.e. that match the search query.
+    ///
+    /// The list state refers to the rows of this list.
+    pub listing: Listing,
+    /// Sort order of the list.
+    pub sort: SortMode,
+    /// Favorites and play history.
+    pub history: History,
+    /// Error of the last history load or save, shown in the UI.
+    pub history_error: Option<String>,
+    /// Error of the last animation that failed to load, shown in the UI.
+    pub animation_error: Option<String>,
     /// Animation widget.
     pub animation: Animation,
+    /// Index of the animation that is played.
+    pub playing: Option<usize>,
+    /// Playlist.
+    pub playlist: Playlist,
+    /// Time when the current animation is finished.
+    pub finished_at: Option<Instant>,
     /// Animation area.
     pub animation_area: Rect,
+    /// Progress bar area.
+    pub progress_area: Rect,
     /// Frame interval for stable FPS.
     pub frame_interval: Duration,
     /// FPS counter widget.
@@ -53,43 +81,80 @@ pub struct App {
 
 impl App {
     /// Construct a new instance of [`App`].
-    pub fn new(event_handler: EventHandler, args: Args) -> Self {
+    pub fn new(event_handler: EventHandler, args: Args) -> Result<Self> {
         let effect = fx::coalesce((800, Interpolation::SineOut));
-        Self {
+        let animations = library::entries(args.file.as_deref(), &args.dir)?
+            .into_iter()
+            .filter(|entry| args.filter.as_ref().is_none_or(|text| entry.matches(text)))
+            .filter(|entry| args.tag.as_ref().is_none_or(|tag| entry.has_tag(tag)))
+            .filter(|entry| !args.safe || entry.is_safe())
+            .collect::<Vec<_>>();
+        let mut app = Self {
             is_running: true,
-            is_toggled: true,
+            is_toggled: !args.kiosk,
             event_handler,
-            list_state: ListState::default().with_selected(Some(0)),
-            animations: Animations::iter().map(|a| a.to_string()).collect(),
-            animation: Animation::default(),
+            list_state: ListState::default(),
+            animations,
+            search: Search::default(),
+            listing: Listing::default(),
+            sort: args.sort,
+            history: History::default(),
+            history_error: None,
+            animation_error: None,
+            animation: Animation {
+                speed: args.speed.unwrap_or(1.),
+                ..Animation::default()
+            },
+            playing: None,
+            playlist: Playlist::new(if args.kiosk {
+                PlayMode::Shuffle
+            } else {
+                args.mode
+            }),
+            finished_at: None,
             animation_area: Rect::default(),
-            frame_interval: Duration::from_secs_f32(1.0 / args.fps),
+            progress_area: Rect::default(),
+            frame_interval: args.frame_interval(),
             fps: Fps::default(),
             effect,
             args,
+        };
+        match History::load() {
+            Ok(history) => app.history = history,
+            Err(e) => app.history_error = Some(e.to_string()),
         }
+        app.update_list();
+        Ok(app)
     }
 
     /// Run the application's main loop.
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         let mut accumulator = Duration::new(0, 0);
         let mut last_tick = Instant::now();
-        let list_width = self
-            .animations
-            .iter()
-            .map(|a| a.len())
-            .max()
-            .unwrap_or_default();
+        let list_width = self.list_width();
         if let Some(position) = self
             .args
             .file
             .as_ref()
-            .and_then(|file| self.animations.iter().position(|anim| file == anim))
+            .and_then(|file| self.animations.iter().position(|anim| anim.refers_to(file)))
         {
             self.is_toggled = false;
-            self.list_state.select(Some(position));
+            self.select(position);
+            if self.animations[position].stream().is_some() {
+                terminal.draw(|frame| self.draw(frame, list_width))?;
+                self.start_animation();
+            }
         } else if self.args.file.is_some() {
-            color_eyre::eyre::bail!("unknown file, please select one of {:?}", self.animations)
+            color_eyre::eyre::bail!(
+                "unknown file, please select one of {:?}",
+                self.animations
+                    .iter()
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<_>>()
+            )
+        }
+        if self.animations.is_empty() {
+            color_eyre::eyre::bail!("no animations found");
         }
         while self.is_running {
             terminal.draw(|frame| self.draw(frame, list_width))?;
@@ -97,6 +162,8 @@ impl App {
             match event {
                 Event::Tick => {
                     self.fps.tick();
+                    self.animation.update();
+                    self.play_next()?;
                     accumulator += last_tick.elapsed();
                     while accumulator >= self.frame_interval {
                         if !self.animation.is_rendered {
@@ -107,11 +174,10 @@ impl App {
                     last_tick = Instant::now();
                 }
                 Event::Key(key_event) => handle_key_events(key_event, &mut self)?,
-                Event::Mouse(_) => {}
+                Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, &mut self)?,
                 Event::Resize(_, _) => {
                     self.animation
-                        .parser
-                        .set_size(self.animation_area.height, self.animation_area.width);
+                        .resize(self.animation_area.height, self.animation_area.width);
                 }
             }
         }
@@ -131,27 +197,106 @@ impl App {
 
         let mut lines = Vec::new();
         let selected = self.list_state.selected().unwrap_or_default();
-        for (i, animation) in self.animations.iter().enumerate() {
+        let name_width = self
+            .animations
+            .iter()
+            .map(|a| a.name.chars().count())
+            .max()
+            .unwrap_or_default();
+        for (i, item) in self.listing.items.iter().enumerate() {
             let distance = (i as isize - selected as isize).abs() as f64;
             let adjusted_brightness = (100. - distance * 2.).max(30.);
-            let color = Color::from_hsl(0.0, 0.0, adjusted_brightness);
-            let line = animation.to_line().style(Style::default().fg(color));
-            lines.push(line);
+            let m = match item {
+                Item::Category {
+                    name,
+                    len,
+                    is_collapsed,
+                } => {
+                    let color = Color::from_hsl(40.0, 80.0, adjusted_brightness * 0.7);
+                    lines.push(
+                        Line::from(vec![
+                            if *is_collapsed { "▸ " } else { "▾ " }.into(),
+                            name.as_str().bold(),
+                            format!(" ({len})").dark_gray(),
+                        ])
+                        .style(Style::default().fg(color)),
+                    );
+                    continue;
+                }
+                Item::Entry(position) => &self.listing.matches[*position],
+            };
+            let animation = &self.animations[m.index];
+            let style = if animation.is_local() {
+                let color = Color::from_hsl(180.0, 50.0, adjusted_brightness);
+                Style::default().fg(color).italic()
+            } else {
+                let color = Color::from_hsl(0.0, 0.0, adjusted_brightness);
+                Style::default().fg(color)
+            };
+            let mut spans = Vec::new();
+            if animation
+                .history_key()
+                .is_some_and(|key| self.history.is_favorite(&key))
+            {
+                spans.push("★ ".yellow());
+            } else {
+                spans.push(Span::raw("  "));
+            }
+            spans.extend(animation.name.chars().enumerate().map(|(i, c)| {
+                if m.indices.contains(&i) {
+                    Span::styled(c.to_string(), Style::default().fg(Color::Yellow).bold())
+                } else {
+                    Span::raw(c.to_string())
+                }
+            }));
+            let tags = animation.tags().join(", ");
+            if !tags.is_empty() {
+                spans.push(Span::raw(
+                    " ".repeat(name_width - animation.name.chars().count() + 1),
+                ));
+                spans.push(tags.dark_gray());
+            }
+            lines.push(Line::from(spans).style(style));
+        }
+
+        let mut list_block = Block::bordered()
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .title(
+                "VT100 Animations"
+                    .fg(Color::Reset)
+                    .bold()
+                    .underlined()
+                    .into_centered_line(),
+            );
+        if self.search.is_active || !self.search.query.is_empty() {
+            list_block = list_block
+                .title(
+                    Title::from(Line::from(vec![
+                        "/".yellow(),
+                        self.search.query.as_str().fg(Color::Reset),
+                        if self.search.is_active {
+                            "▏".into()
+                        } else {
+                            "".into()
+                        },
+                    ]))
+                    .alignment(Alignment::Left)
+                    .position(Position::Bottom),
+                )
+                .title(
+                    Title::from(format!(
+                        "{}/{}",
+                        self.listing.matches.len(),
+                        self.animations.len()
+                    ))
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+                );
         }
 
         frame.render_stateful_widget(
             List::new(lines)
-                .block(
-                    Block::bordered()
-                        .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
-                        .title(
-                            "VT100 Animations"
-                                .fg(Color::Reset)
-                                .bold()
-                                .underlined()
-                                .into_centered_line(),
-                        ),
-                )
+                .block(list_block)
                 .highlight_style(if self.animation.is_rendered {
                     Style::default().fg(Color::Magenta).slow_blink()
                 } else {
@@ -165,7 +310,7 @@ impl App {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓"));
-            let mut scrollbar_state = ScrollbarState::new(self.animations.len())
+            let mut scrollbar_state = ScrollbarState::new(self.listing.items.len())
                 .position(self.list_state.selected().unwrap_or_default());
             frame.render_stateful_widget(
                 scrollbar,
@@ -188,19 +333,21 @@ impl App {
             .title(
                 Title::from(Line::from(vec![
                     "|".into(),
-                    self.list_state
-                        .selected()
-                        .map(|i| self.animations[i].clone())
+                    self.selected()
+                        .map(|i| self.animations[i].name.clone())
+                        .or_else(|| self.selected_category().map(|(name, _)| name))
                         .unwrap_or_default()
                         .fg(Color::Reset)
                         .bold(),
                     ": ".into(),
-                    self.list_state
-                        .selected()
-                        .and_then(|i| {
-                            descriptions()
-                                .get(self.animations[i].as_str())
-                                .map(|v| v.to_string())
+                    self.selected()
+                        .and_then(|i| match &self.animation.sauce {
+                            Some(sauce) if self.playing == Some(i) => Some(sauce.to_string()),
+                            _ => self.animations[i].description(),
+                        })
+                        .or_else(|| {
+                            self.selected_category()
+                                .map(|(_, len)| format!("{len} animations"))
                         })
                         .unwrap_or_default()
                         .fg(Color::Reset)
@@ -210,28 +357,73 @@ impl App {
                 .alignment(Alignment::Left)
                 .position(Position::Bottom),
             );
-
-        self.animation_area = area[1].inner(Margin {
-            vertical: 1,
-            horizontal: 1,
-        });
-
-        if !self.animation.is_rendered {
+        for error in [&self.animation_error, &self.history_error]
+            .into_iter()
+            .flatten()
+        {
             block = block.title(
                 Title::from(Line::from(vec![
                     "|".into(),
-                    "fps".italic(),
-                    ": ".into(),
-                    self.fps.to_string().into(),
+                    error.as_str().red(),
                     "|".into(),
                 ]))
                 .alignment(Alignment::Right)
-                .position(Position::Top),
+                .position(Position::Bottom),
+            );
+        }
+
+        let [animation_area, progress_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area[1].inner(
+                Margin {
+                    vertical: 1,
+                    horizontal: 1,
+                },
+            ));
+        self.animation_area = animation_area;
+
+        if !self.animation.is_rendered {
+            let mut spans = Vec::new();
+            if self.animation.is_paused {
+                spans.extend(["|".into(), "paused".italic().yellow(), "|".into()]);
+            }
+            if self.sort != SortMode::Name {
+                spans.extend([
+                    "|".into(),
+                    "sort".italic(),
+                    ": ".into(),
+                    self.sort.to_string().into(),
+                    "|".into(),
+                ]);
+            }
+            spans.extend([
+                "|".into(),
+                "mode".italic(),
+                ": ".into(),
+                self.playlist.mode.to_string().into(),
+                "|".into(),
+                "|".into(),
+                "speed".italic(),
+                ": ".into(),
+                format!("{}x", self.animation.speed).into(),
+                "|".into(),
+                "|".into(),
+                "fps".italic(),
+                ": ".into(),
+                self.fps.to_string().into(),
+                "|".into(),
+            ]);
+            block = block.title(
+                Title::from(Line::from(spans))
+                    .alignment(Alignment::Right)
+                    .position(Position::Top),
             );
         }
 
         frame.render_widget(block, area[1]);
         frame.render_widget(&mut self.animation, self.animation_area);
+        if !self.animation.data.is_empty() {
+            self.draw_progress(frame, progress_area);
+        }
         frame.render_effect(
             &mut self.effect,
             self.animation_area,
@@ -239,18 +431,349 @@ impl App {
         );
     }
 
-    pub fn start_animation(&mut self) {
-        let selected = self.list_state.selected().unwrap_or_default();
-        let data = Animations::get(&self.animations[selected].clone())
-            .expect("no animation found")
-            .data
-            .into_owned();
-        self.animation = Animation {
-            is_rendered: false,
-            reader: BufReader::new(Cursor::new(data)),
-            parser: Parser::new(self.animation_area.height, self.animation_area.width, 0),
-            buffer: String::new(),
+    /// Renders the progress bar.
+    fn draw_progress(&mut self, frame: &mut Frame, area: Rect) {
+        let label = Line::from(vec![
+            " ".into(),
+            format_size(self.animation.position).into(),
+            "/".dark_gray(),
+            format_size(self.animation.data.len()).into(),
+            " ".into(),
+            format_duration(self.animation.clock).into(),
+            "/".dark_gray(),
+            format_duration(self.animation.duration()).into(),
+        ]);
+        let [gauge_area, label_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(label.width() as u16),
+        ])
+        .areas(area);
+        self.progress_area = gauge_area;
+        frame.render_widget(
+            LineGauge::default()
+                .filled_style(Style::default().fg(if self.animation.is_paused {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                }))
+                .unfilled_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .label("")
+                .ratio(self.animation.progress()),
+            gauge_area,
+        );
+        frame.render_widget(label, label_area);
+    }
+
+    /// Returns the playback pacing based on the arguments.
+    pub fn pacing(&self) -> Pacing {
+        self.args.pacing()
+    }
+
+    /// Starts the next animation in the playlist if the current one is finished
+    /// and its final frame is held long enough.
+    ///
+    /// The selected animation is started if nothing is played yet.
+    fn play_next(&mut self) -> Result<()> {
+        if !self.animation.is_rendered || self.playlist.mode == PlayMode::Once {
+            return Ok(());
+        }
+        if self.playing.is_some() {
+            let finished_at = *self.finished_at.get_or_insert_with(Instant::now);
+            if finished_at.elapsed() < Duration::from_secs(self.args.hold) {
+                return Ok(());
+            }
+        }
+        if self.listing.matches.is_empty() {
+            return Ok(());
+        }
+        let selected = self.selected_match().unwrap_or_default();
+        // the cursor might be on a category or another animation
+        let current = self
+            .playing
+            .and_then(|index| self.listing.matches.iter().position(|m| m.index == index))
+            .unwrap_or(selected);
+        let len = self.listing.matches.len();
+        // the shuffle starts with a random animation unless a file is given
+        let mut next = if self.playing.is_none()
+            && (self.playlist.mode != PlayMode::Shuffle || self.args.file.is_some())
+        {
+            Some(selected)
+        } else {
+            self.playlist.next(current, len)
+        };
+        // skip the animations that fail to load, trying each of them once
+        for _ in 0..len {
+            let Some(position) = next else {
+                break;
+            };
+            self.select(self.listing.matches[position].index);
+            if self.start_animation() {
+                break;
+            }
+            next = self
+                .playlist
+                .next(position, len)
+                .filter(|next| *next != position);
+        }
+        Ok(())
+    }
+
+    /// Returns the width of the animation list.
+    fn list_width(&self) -> usize {
+        let name_width = self
+            .animations
+            .iter()
+            .map(|a| a.name.chars().count())
+            .max()
+            .unwrap_or_default();
+        let tag_width = self
+            .animations
+            .iter()
+            .map(|a| a.tags().join(", ").chars().count())
+            .max()
+            .unwrap_or_default();
+        let category_width = self
+            .listing
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Category { name, len, .. } => {
+                    Some(name.chars().count() + len.to_string().len() + 5)
+                }
+                Item::Entry(_) => None,
+            })
+            .max()
+            .unwrap_or_default();
+        (2 + name_width + if tag_width > 0 { tag_width + 1 } else { 0 }).max(category_width)
+    }
+
+    /// Returns the position of the selected animation in the listed animations.
+    fn selected_match(&self) -> Option<usize> {
+        match self
+            .list_state
+            .selected()
+            .and_then(|i| self.listing.items.get(i))
+        {
+            Some(Item::Entry(position)) => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the selected animation.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected_match()
+            .map(|position| self.listing.matches[position].index)
+    }
+
+    /// Returns the name and the number of animations of the selected category.
+    pub fn selected_category(&self) -> Option<(String, usize)> {
+        match self
+            .list_state
+            .selected()
+            .and_then(|i| self.listing.items.get(i))
+        {
+            Some(Item::Category { name, len, .. }) => Some((name.clone(), *len)),
+            _ => None,
+        }
+    }
+
+    /// Selects the animation with the given index if it is listed.
+    ///
+    /// Its category is expanded if it is collapsed.
+    pub fn select(&mut self, index: usize) {
+        if let Some(row) = self.listing.reveal(&self.animations, index) {
+            self.list_state.select(Some(row));
+        }
+    }
+
+    /// Collapses or expands the selected category.
+    pub fn toggle_category(&mut self) {
+        if let Some((name, _)) = self.selected_category() {
+            self.listing.toggle(&self.animations, &name);
+        }
+    }
+
+    /// Collapses or expands all the categories.
+    ///
+    /// The category of the selected animation is selected if it is collapsed.
+    pub fn toggle_categories(&mut self) {
+        let selected = self.selected_match();
+        let category = self.selected_category().map(|(name, _)| name).or_else(|| {
+            self.selected()
+                .map(|i| self.animations[i].category().to_string())
+        });
+        self.listing.toggle_all(&self.animations);
+        let row = selected
+            .and_then(|position| {
+                self.listing
+                    .items
+                    .iter()
+                    .position(|item| *item == Item::Entry(position))
+            })
+            .or_else(|| category.and_then(|name| self.listing.category_row(&name)));
+        self.list_state = ListState::default().with_selected(row);
+    }
+
+    /// Updates the listed animations after the search query is changed.
+    ///
+    /// The best match is selected, or the previously selected animation if there is
+    /// no text to match. The animations are grouped by category unless the text is
+    /// fuzzy matched.
+    pub fn update_search(&mut self) {
+        self.update_list();
+        *self.list_state.offset_mut() = 0;
+        if self.search.is_fuzzy() {
+            self.list_state.select(self.listing.first_entry());
+        }
+    }
+
+    /// Updates the listed animations, e.g. after the history or the sort order is changed.
+    ///
+    /// The selected animation or category stays selected if it is still listed.
+    pub fn update_list(&mut self) {
+        let selected = self.selected();
+        let category = self.selected_category().map(|(name, _)| name);
+        let is_fuzzy = self.search.is_fuzzy();
+        let mut matches = self.search.filter(&self.animations);
+        if !is_fuzzy {
+            self.sort
+                .sort(&mut matches, &self.animations, &self.history);
+        }
+        self.listing.recent = self.recent();
+        self.listing.update(&self.animations, matches, !is_fuzzy);
+        self.reselect(selected, category);
+    }
+
+    /// Updates the recently played animations in the list.
+    ///
+    /// The order of the list is kept, e.g. for the play modes.
+    fn update_recent(&mut self) {
+        let selected = self.selected();
+        let category = self.selected_category().map(|(name, _)| name);
+        self.listing.set_recent(&self.animations, self.recent());
+        self.reselect(selected, category);
+    }
+
+    /// Returns the indices of the recently played animations.
+    fn recent(&self) -> Vec<usize> {
+        self.history
+            .recent(RECENT_LEN)
+            .into_iter()
+            .filter_map(|key| {
+                self.animations
+                    .iter()
+                    .position(|entry| entry.history_key().as_deref() == Some(key))
+            })
+            .collect()
+    }
+
+    /// Selects the given animation or category after the list is changed.
+    ///
+    /// The first animation is selected if they are not listed anymore.
+    fn reselect(&mut self, selected: Option<usize>, category: Option<String>) {
+        let row = category
+            .and_then(|name| self.listing.category_row(&name))
+            .or_else(|| selected.and_then(|index| self.listing.reveal(&self.animations, index)))
+            .or_else(|| self.listing.first_entry());
+        self.list_state.select(row);
+    }
+
+    /// Cycles through the sort orders of the list.
+    pub fn next_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.update_list();
+    }
+
+    /// Stars or unstars the selected animation.
+    pub fn toggle_favorite(&mut self) {
+        let Some(key) = self
+            .selected()
+            .and_then(|i| self.animations[i].history_key())
+        else {
+            return;
+        };
+        self.history.toggle_favorite(&key);
+        self.save_history();
+        self.update_list();
+    }
+
+    /// Saves the history, showing the error in the UI instead of failing.
+    ///
+    /// The error is kept, e.g. the history is not saved after it fails to load.
+    fn save_history(&mut self) {
+        if let Err(e) = self.history.save() {
+            self.history_error = Some(e.to_string());
+        }
+    }
+
+    /// Starts the selected animation and returns whether it is started.
+    ///
+    /// The error is shown in the UI instead of failing if the animation fails to load.
+    pub fn start_animation(&mut self) -> bool {
+        let Some(selected) = self.selected() else {
+            return false;
         };
+        // the speed is kept between the animations if it is given via the arguments
+        let speed = match self.args.speed {
+            Some(_) => self.animation.speed,
+            None => self.args.playback_speed(&self.animations[selected]),
+        };
+        // the data of a stream is given back so that it can be replayed
+        if let Some(stream) = self.playing.and_then(|i| self.animations[i].stream()) {
+            stream.restore(mem::take(&mut self.animation.data));
+        }
+        let result = self.load_animation(selected);
+        self.animation_error = result
+            .as_ref()
+            .err()
+            .map(|e| format!("{}: {e}", self.animations[selected].name));
+        self.animation = result.unwrap_or_default();
+        self.animation.set_speed(speed);
+        self.playing = Some(selected);
+        self.finished_at = None;
+        if self.animation_error.is_some() {
+            return false;
+        }
         self.effect.reset();
+        if let Some(key) = self.animations[selected].history_key() {
+            self.history.record_play(&key);
+            self.save_history();
+            self.update_recent();
+        }
+        true
+    }
+
+    /// Loads the given animation for the animation area.
+    fn load_animation(&self, index: usize) -> Result<Animation> {
+        let entry = &self.animations[index];
+        let (rows, cols) = (self.animation_area.height, self.animation_area.width);
+        Ok(match entry.stream() {
+            Some(stream) => Animation::from_stream(stream.clone(), self.pacing(), rows, cols),
+            None => {
+                let timing = self
+                    .args
+                    .timing
+                    .as_deref()
+                    .filter(|_| self.args.file.as_ref().is_some_and(|f| entry.refers_to(f)));
+                let recording = format::decode_with_timing(
+                    &entry.name,
+                    entry.load()?,
+                    timing,
+                    self.args.encoding,
+                )?;
+                Animation::from_recording(recording, self.pacing(), rows, cols)
+            }
+        })
     }
 }
+
+/// Formats the given number of bytes in KiB.
+fn format_size(bytes: usize) -> String {
+    format!("{:.1}KiB", bytes as f64 / 1024.)
+}
+
+/// Formats the given duration as minutes and seconds.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
@@ -1,4 +1,17 @@
+use crate::{
+    animation::{MAX_SPEED, MIN_SPEED},
+    config::Config,
+    event::KeyChord,
+    export::{ExportFormat, SnapshotFormat},
+    format::encoding::Encoding,
+    library::Entry,
+    listing::SortMode,
+    pacing::Pacing,
+    playlist::PlayMode,
+};
 pub use clap::Parser;
+use clap::Subcommand;
+use std::{path::PathBuf, time::Duration};
 
 /// Argument parser powered by [`clap`].
 #[derive(Clone, Debug, Default, Parser)]
@@ -7,6 +20,7 @@ pub use clap::Parser;
     author = clap::crate_authors!("\n"),
     about,
     rename_all_env = "screaming-snake",
+    args_conflicts_with_subcommands = true,
     help_template = "\
 {before-help}{name} {version}
 {author-with-newline}{about-with-newline}
@@ -17,16 +31,179 @@ pub use clap::Parser;
 ",
 )]
 pub struct Args {
-    /// Play a specific file.
+    /// Subcommand.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Play a specific file (embedded animation or a path on disk).
     pub file: Option<String>,
 
+    /// Timing file of a typescript recorded via `script --timing`.
+    #[arg(long, value_name = "FILE", global = true)]
+    pub timing: Option<PathBuf>,
+
+    /// Add the files in the given directory to the list.
+    #[arg(short, long, value_name = "DIR", global = true)]
+    pub dir: Vec<PathBuf>,
+
     /// Terminal tick rate.
-    #[arg(env, short, long, value_name = "MS", default_value = "100")]
+    #[arg(
+        env,
+        global = true,
+        short,
+        long,
+        value_name = "MS",
+        default_value = "100"
+    )]
     pub tick_rate: u64,
 
     /// Target FPS value.
-    #[arg(env, short, long, default_value = "60.0")]
+    #[arg(env, global = true, short, long, default_value = "60.0")]
     pub fps: f32,
+
+    /// Emulate a serial line with the given baud rate (e.g. 1200, 2400, 9600, 19200).
+    #[arg(
+        global = true,
+        short,
+        long,
+        value_name = "RATE",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub baud: Option<u32>,
+
+    /// Character encoding of the files.
+    #[arg(global = true, short, long, value_enum, default_value_t = Encoding::default())]
+    pub encoding: Encoding,
+
+    /// Playback speed multiplier [default: the recommended speed of the animation or 1.0].
+    #[arg(global = true, short, long, value_name = "FACTOR", value_parser = parse_speed)]
+    pub speed: Option<f64>,
+
+    /// Play mode.
+    #[arg(global = true, short, long, value_enum, default_value_t = PlayMode::default())]
+    pub mode: PlayMode,
+
+    /// Sort order of the list.
+    #[arg(global = true, long, value_enum, default_value_t = SortMode::default())]
+    pub sort: SortMode,
+
+    /// Only list the animations that contain the given text in their name or description.
+    #[arg(global = true, long, value_name = "TEXT")]
+    pub filter: Option<String>,
+
+    /// Only list the animations with the given tag (e.g. christmas).
+    #[arg(global = true, long, value_name = "TAG")]
+    pub tag: Option<String>,
+
+    /// Hide the animations that are not suitable for shared screens.
+    #[arg(global = true, long)]
+    pub safe: bool,
+
+    /// Configuration file [default: ~/.config/theattyr/config.toml].
+    #[arg(global = true, long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Time to hold the final frame before playing the next animation.
+    #[arg(global = true, long, value_name = "SECS", default_value = "3")]
+    pub hold: u64,
+
+    /// Run as a screensaver: hide the list, shuffle the animations and ignore the input.
+    #[arg(global = true, short, long)]
+    pub kiosk: bool,
+
+    /// Play the file directly on the terminal without the user interface.
+    #[arg(global = true, short, long)]
+    pub raw: bool,
+
+    /// Key combination to exit the kiosk mode.
+    #[arg(global = true, long, value_name = "KEYS", default_value = "ctrl-c")]
+    pub exit_key: KeyChord,
+}
+
+/// Subcommands.
+#[derive(Clone, Debug, Subcommand)]
+pub enum Command {
+    /// Play a specific file.
+    Play {
+        /// Embedded animation or a path on disk.
+        file: String,
+    },
+    /// Export an animation to a file.
+    Export {
+        /// Embedded animation or a path on disk.
+        file: String,
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = ExportFormat::default())]
+        format: ExportFormat,
+        /// Position of the exported frame for the single frame formats (e.g. 50%).
+        ///
+        /// The final frame is exported by default.
+        #[arg(long, value_name = "PERCENT", value_parser = parse_percentage)]
+        at: Option<f64>,
+        /// Output file (defaults to the standard output).
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Play a file to completion and print the final screen.
+    Snapshot {
+        /// Embedded animation or a path on disk.
+        file: String,
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = SnapshotFormat::default())]
+        format: SnapshotFormat,
+    },
+}
+
+impl Args {
+    /// Applies the settings in the configuration file that are not set via arguments.
+    pub fn merge(&mut self, config: &Config) {
+        self.safe |= config.safe;
+    }
+
+    /// Returns the interval between the frames for the target FPS value.
+    pub fn frame_interval(&self) -> Duration {
+        Duration::from_secs_f32(1.0 / self.fps)
+    }
+
+    /// Returns the playback speed of the given animation.
+    ///
+    /// The speed in the arguments overrides the recommended speed in the manifest.
+    pub fn playback_speed(&self, entry: &Entry) -> f64 {
+        self.speed
+            .or_else(|| entry.metadata().and_then(|metadata| metadata.speed))
+            .unwrap_or(1.)
+    }
+
+    /// Returns the playback pacing.
+    pub fn pacing(&self) -> Pacing {
+        match self.baud {
+            Some(rate) => Pacing::Baud(rate),
+            None => Pacing::Line(self.frame_interval()),
+        }
+    }
+}
+
+/// Parses and validates the playback speed.
+fn parse_speed(value: &str) -> Result<f64, String> {
+    let speed = value.parse::<f64>().map_err(|e| e.to_string())?;
+    if (MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        Ok(speed)
+    } else {
+        Err(format!("must be between {MIN_SPEED} and {MAX_SPEED}"))
+    }
+}
+
+/// Parses a percentage (e.g. `50%`) as a ratio.
+fn parse_percentage(value: &str) -> Result<f64, String> {
+    let percentage = value
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|e| e.to_string())?;
+    if (0. ..=100.).contains(&percentage) {
+        Ok(percentage / 100.)
+    } else {
+        Err(String::from("must be between 0% and 100%"))
+    }
 }
 
 #[cfg(test)]
@@ -36,5 +213,10 @@ mod tests {
     #[test]
     fn test_args() {
         Args::command().debug_assert();
+        let mut args = Args::default();
+        assert_eq!(2., args.playback_speed(&Entry::embedded("fishy2.vt")));
+        assert_eq!(1., args.playback_speed(&Entry::embedded("globe.vt")));
+        args.speed = Some(0.5);
+        assert_eq!(0.5, args.playback_speed(&Entry::embedded("fishy2.vt")));
     }
 }
@@ -0,0 +1,66 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+
+/// Name of the configuration file.
+const FILE_NAME: &str = "config.toml";
+
+/// Configuration file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Hide the animations that are rated as mature.
+    pub safe: bool,
+}
+
+impl Config {
+    /// Returns the default path of the configuration file.
+    ///
+    /// e.g. `~/.config/theattyr/config.toml` on Linux.
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join(FILE_NAME))
+    }
+
+    /// Loads the configuration from the given file or the default path.
+    ///
+    /// The default configuration is used if there is no file at the default path.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, is_default) = match path {
+            Some(path) => (path.to_path_buf(), false),
+            None => match Self::path() {
+                Some(path) => (path, true),
+                None => return Ok(Self::default()),
+            },
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| eyre!("invalid configuration in {}: {e}", path.display())),
+            Err(e) if is_default && e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(eyre!("failed to read {}: {e}", path.display())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("theattyr-{}.toml", std::process::id()));
+        fs::write(&path, "safe = true\n")?;
+        let config = Config::load(Some(&path));
+        fs::write(&path, "unknown = 1\n")?;
+        let invalid = Config::load(Some(&path));
+        fs::remove_file(&path)?;
+        assert_eq!(Config { safe: true }, config?);
+        assert!(invalid.is_err());
+        assert!(Config::load(Some(&path)).is_err());
+        assert_eq!(Config::default(), toml::from_str("")?);
+        Ok(())
+    }
+}
@@ -1,12 +1,20 @@
-use crate::app::App;
+use crate::{animation::Step, app::App};
 use color_eyre::Result;
-use ratatui::crossterm::event::{
-    self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent,
+use ratatui::{
+    crossterm::event::{
+        self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
+    layout::Position,
 };
+use std::str::FromStr;
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
+/// Amount of time to seek with the arrow keys.
+const SEEK_STEP: Duration = Duration::from_secs(5);
+
 /// Terminal events.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Event {
@@ -20,6 +28,74 @@ pub enum Event {
     Resize(u16, u16),
 }
 
+/// Key combination such as `ctrl-c` or `alt+q`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    /// Key modifiers.
+    pub modifiers: KeyModifiers,
+    /// Key code.
+    pub code: KeyCode,
+}
+
+impl Default for KeyChord {
+    fn default() -> Self {
+        Self {
+            modifiers: KeyModifiers::CONTROL,
+            code: KeyCode::Char('c'),
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        let (modifiers, key) = match s.rsplit_once(['-', '+']) {
+            Some((modifiers, key)) if !key.is_empty() => (modifiers, key),
+            _ => ("", s.as_str()),
+        };
+        let modifiers = modifiers
+            .split(['-', '+'])
+            .filter(|m| !m.is_empty())
+            .try_fold(KeyModifiers::NONE, |modifiers, m| {
+                Ok(modifiers
+                    | match m {
+                        "ctrl" | "control" => KeyModifiers::CONTROL,
+                        "alt" | "meta" => KeyModifiers::ALT,
+                        "shift" => KeyModifiers::SHIFT,
+                        "super" => KeyModifiers::SUPER,
+                        _ => return Err(format!("unknown modifier: {m}")),
+                    })
+            })?;
+        let code = match key {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next().unwrap_or_default()),
+            _ => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n) => KeyCode::F(n),
+                None => return Err(format!("unknown key: {key}")),
+            },
+        };
+        Ok(Self { modifiers, code })
+    }
+}
+
+impl KeyChord {
+    /// Returns whether the given key event matches the key combination.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let code = match key.code {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            code => code,
+        };
+        code == self.code
+            && key.modifiers - KeyModifiers::SHIFT == self.modifiers - KeyModifiers::SHIFT
+    }
+}
+
 /// Terminal event handler.
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -91,24 +167,179 @@ impl EventHandler {
 
 /// Handles the key events and updates the application state.
 pub fn handle_key_events(key: KeyEvent, app: &mut App) -> Result<()> {
+    if app.args.kiosk {
+        if app.args.exit_key.matches(&key) {
+            app.is_running = false;
+        }
+        return Ok(());
+    }
+    if app.search.is_active {
+        return handle_search_key_events(key, app);
+    }
     match (key.modifiers, key.code) {
+        (_, KeyCode::Esc) if !app.search.query.is_empty() => {
+            app.search.query.clear();
+            app.update_search();
+        }
         (_, KeyCode::Esc | KeyCode::Char('q'))
         | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => {
             app.is_running = false;
         }
+        (_, KeyCode::Char('/')) => {
+            app.search.is_active = true;
+            app.is_toggled = true;
+        }
         (_, KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J')) => {
             app.list_state.select_next();
         }
         (_, KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K')) => {
             app.list_state.select_previous();
         }
+        (_, KeyCode::Enter) if app.selected_category().is_some() => {
+            app.toggle_category();
+        }
         (_, KeyCode::Enter) => {
             app.start_animation();
         }
+        (_, KeyCode::Char('c') | KeyCode::Char('C')) => {
+            app.toggle_categories();
+        }
         (_, KeyCode::Tab) => {
             app.is_toggled = !app.is_toggled;
         }
+        (_, KeyCode::Char(' ')) => {
+            app.animation.toggle_pause();
+        }
+        (_, KeyCode::Char('n') | KeyCode::Char('N')) => {
+            app.animation.step(Step::Line);
+        }
+        (_, KeyCode::Char('b') | KeyCode::Char('B')) => {
+            app.animation.step(Step::Byte);
+        }
+        (_, KeyCode::Char('e') | KeyCode::Char('E')) => {
+            app.animation.step(Step::Sequence);
+        }
+        (_, KeyCode::Left) => {
+            app.animation
+                .seek_time(app.animation.clock.saturating_sub(SEEK_STEP));
+        }
+        (_, KeyCode::Right) => {
+            app.animation.seek_time(app.animation.clock + SEEK_STEP);
+        }
+        (_, KeyCode::Char('m') | KeyCode::Char('M')) => {
+            app.playlist.mode = app.playlist.mode.next();
+        }
+        (_, KeyCode::Char('f') | KeyCode::Char('F')) => {
+            app.toggle_favorite();
+        }
+        (_, KeyCode::Char('s') | KeyCode::Char('S')) => {
+            app.next_sort();
+        }
+        (_, KeyCode::Char('+') | KeyCode::Char('=')) => {
+            app.animation.set_speed(app.animation.speed * 2.);
+        }
+        (_, KeyCode::Char('-')) => {
+            app.animation.set_speed(app.animation.speed / 2.);
+        }
+        (_, KeyCode::Char(c @ '0'..='9')) => {
+            app.animation
+                .seek_ratio(c.to_digit(10).unwrap_or_default() as f64 / 10.);
+        }
         _ => {}
     }
     Ok(())
 }
+
+/// Handles the key events while the search prompt is active.
+fn handle_search_key_events(key: KeyEvent, app: &mut App) -> Result<()> {
+    match (key.modifiers, key.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => {
+            app.is_running = false;
+        }
+        (_, KeyCode::Esc) => {
+            app.search.is_active = false;
+            app.search.query.clear();
+            app.update_search();
+        }
+        (_, KeyCode::Enter) => {
+            app.search.is_active = false;
+            if app.selected_category().is_some() {
+                app.toggle_category();
+            } else {
+                app.start_animation();
+            }
+        }
+        (_, KeyCode::Down) => {
+            app.list_state.select_next();
+        }
+        (_, KeyCode::Up) => {
+            app.list_state.select_previous();
+        }
+        (_, KeyCode::Backspace) => {
+            app.search.query.pop();
+            app.update_search();
+        }
+        (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            app.search.query.push(c);
+            app.update_search();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles the mouse events and updates the application state.
+pub fn handle_mouse_events(mouse: MouseEvent, app: &mut App) -> Result<()> {
+    if app.args.kiosk {
+        return Ok(());
+    }
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left) => {
+            let area = app.progress_area;
+            if area.contains(Position::new(mouse.column, mouse.row)) && area.width > 1 {
+                app.animation
+                    .seek_ratio((mouse.column - area.x) as f64 / (area.width - 1) as f64);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_chord() {
+        let chord = KeyChord::from_str("Ctrl-Alt+x").expect("invalid chord");
+        assert_eq!(KeyModifiers::CONTROL | KeyModifiers::ALT, chord.modifiers);
+        assert_eq!(KeyCode::Char('x'), chord.code);
+        assert!(chord.matches(&KeyEvent::new(
+            KeyCode::Char('X'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT
+        )));
+        assert!(!chord.matches(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)));
+        assert_eq!(Ok(KeyChord::default()), KeyChord::from_str("ctrl-c"));
+        assert_eq!(
+            KeyCode::Char('-'),
+            KeyChord::from_str("-")
+                .map(|c| c.code)
+                .unwrap_or(KeyCode::Null)
+        );
+        assert_eq!(
+            KeyCode::F(10),
+            KeyChord::from_str("f10")
+                .map(|c| c.code)
+                .unwrap_or(KeyCode::Null)
+        );
+        assert_eq!(
+            KeyCode::Esc,
+            KeyChord::from_str("esc")
+                .map(|c| c.code)
+                .unwrap_or(KeyCode::Null)
+        );
+        assert!(KeyChord::from_str("hyper-x").is_err());
+        assert!(KeyChord::from_str("foo").is_err());
+    }
+}
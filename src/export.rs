@@ -0,0 +1,182 @@
+pub mod charset;
+pub mod gif;
+pub mod html;
+pub mod svg;
+pub mod terminal;
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    format::{self, asciicast, Recording},
+    library::{self, Entry},
+    pacing::{Frame, Timeline},
+    Args,
+};
+use terminal::Terminal;
+
+/// Terminal size as rows and columns for the formats without a size.
+const DEFAULT_SIZE: (u16, u16) = (24, 80);
+
+/// Interval for polling the streams until they are finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Export format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// asciicast v2 (asciinema).
+    #[default]
+    Cast,
+    /// Animated GIF.
+    Gif,
+    /// SVG image of a single frame.
+    Svg,
+    /// Self-contained HTML player.
+    Html,
+}
+
+/// Snapshot format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotFormat {
+    /// Plain text.
+    #[default]
+    Text,
+    /// ANSI escape sequences.
+    Ansi,
+}
+
+/// Loads the given file and decodes it.
+///
+/// Streams are read until they are finished.
+fn load(args: &Args, file: &str) -> Result<(Entry, Recording)> {
+    let entry = library::find(file, &args.dir)?;
+    let data = match entry.stream() {
+        Some(stream) => {
+            let mut data = Vec::new();
+            while !stream.receive(&mut data) {
+                thread::sleep(POLL_INTERVAL);
+            }
+            data
+        }
+        None => entry.load()?,
+    };
+    let recording =
+        format::decode_with_timing(&entry.name, data, args.timing.as_deref(), args.encoding)?;
+    Ok((entry, recording))
+}
+
+/// Returns the terminal size of the given recording as rows and columns.
+///
+/// The original size of the embedded animations is used for the formats without a
+/// size, otherwise it is defaulted.
+fn terminal_size(entry: &Entry, recording: &Recording) -> (u16, u16) {
+    recording
+        .size
+        .or_else(|| entry.metadata().and_then(|metadata| metadata.size))
+        .unwrap_or((DEFAULT_SIZE.0, recording.width().unwrap_or(DEFAULT_SIZE.1)))
+}
+
+/// Exports the given file to the output file or the standard output.
+///
+/// The animation is paced the same way as it is played, i.e. a line per frame or
+/// at the given baud rate, with the given playback speed. Single frame formats use
+/// the frame at the given position (from 0 to 1) or the final frame.
+pub fn export(
+    args: &Args,
+    file: &str,
+    format: ExportFormat,
+    at: Option<f64>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let (entry, recording) = load(args, file)?;
+    let size = terminal_size(&entry, &recording);
+    let timeline = match recording.frames {
+        Some(frames) => Timeline::Frames(frames),
+        None => Timeline::new(args.pacing(), &recording.data),
+    };
+    let frames = timeline
+        .frames(recording.data.len(), args.frame_interval())
+        .into_iter()
+        .map(|frame| Frame {
+            time: frame.time.div_f64(args.playback_speed(&entry)),
+            ..frame
+        })
+        .collect::<Vec<_>>();
+    let writer: Box<dyn Write> = match output {
+        Some(path) => {
+            Box::new(BufWriter::new(File::create(path).map_err(|e| {
+                eyre!("failed to create {}: {e}", path.display())
+            })?))
+        }
+        None => Box::new(io::stdout().lock()),
+    };
+    let result = match format {
+        ExportFormat::Cast => {
+            asciicast::encode(writer, &recording.data, &frames, size, Some(&entry.name))
+        }
+        ExportFormat::Gif => gif::encode(
+            writer,
+            &recording.data,
+            &frames,
+            size,
+            Duration::from_secs(args.hold),
+        ),
+        ExportFormat::Svg => svg::encode(writer, &recording.data, &frames, size, at),
+        ExportFormat::Html => html::encode(
+            writer,
+            &recording.data,
+            &frames,
+            size,
+            &entry.name,
+            Duration::from_secs(args.hold),
+        ),
+    };
+    match result {
+        // e.g. piped to `head`, the error is wrapped by the encoders
+        Err(e)
+            if e.chain().any(|e| {
+                e.downcast_ref::<io::Error>()
+                    .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+            }) =>
+        {
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Plays the given file to completion and prints the final screen to the standard output.
+pub fn snapshot(args: &Args, file: &str, format: SnapshotFormat) -> Result<()> {
+    let (entry, recording) = load(args, file)?;
+    let (rows, cols) = terminal_size(&entry, &recording);
+    let mut terminal = Terminal::new(rows, cols);
+    terminal.process(&recording.data);
+    let screen = terminal.screen();
+    let mut stdout = io::stdout().lock();
+    let result = match format {
+        SnapshotFormat::Text => writeln!(stdout, "{}", screen.contents()),
+        SnapshotFormat::Ansi => stdout
+            .write_all(&screen.contents_formatted())
+            .and_then(|_| {
+                if screen.hide_cursor() {
+                    stdout.write_all(b"\x1b[?25h")
+                } else {
+                    Ok(())
+                }
+            })
+            .and_then(|_| stdout.write_all(b"\x1b[0m\r\n")),
+    };
+    match result.and_then(|_| stdout.flush()) {
+        // e.g. piped to `head`
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
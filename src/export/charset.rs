@@ -0,0 +1,105 @@
+//! DEC special graphics character set.
+//!
+//! [`vt100`] ignores the character set designations, so the line drawing characters
+//! are translated to Unicode before the output is processed.
+
+/// Characters of the DEC special graphics set for the bytes from `0x5f` to `0x7e`.
+const SPECIAL_GRAPHICS: [char; 32] = [
+    ' ', '◆', '▒', '␉', '␌', '␍', '␊', '°', '±', '␤', '␋', '┘', '┐', '┌', '└', '┼', '⎺', '⎻', '─',
+    '⎼', '⎽', '├', '┤', '┴', '┬', '│', '≤', '≥', 'π', '≠', '£', '·',
+];
+
+/// Parser state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum State {
+    /// Printable characters and control codes.
+    #[default]
+    Ground,
+    /// After an escape.
+    Escape,
+    /// Designation of the given character set (G0 or G1).
+    Designate(usize),
+    /// Control sequence.
+    Csi,
+    /// Control string (OSC, DCS, etc.) that is terminated by BEL or ST.
+    String,
+    /// Escape inside of a control string.
+    StringEscape,
+}
+
+/// Translator of the DEC special graphics characters.
+///
+/// The state is kept between the calls so that the output can be translated in chunks.
+#[derive(Clone, Debug, Default)]
+pub struct Charset {
+    /// Parser state.
+    state: State,
+    /// Is the special graphics set designated as G0 and G1?
+    graphics: [bool; 2],
+    /// Is G1 invoked via shift out?
+    is_shifted: bool,
+}
+
+impl Charset {
+    /// Translates the given output.
+    pub fn translate(&mut self, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len());
+        for &byte in data {
+            self.state = match (self.state, byte) {
+                (_, 0x1b) if self.state != State::String => State::Escape,
+                (State::Ground, 0x0e) => {
+                    self.is_shifted = true;
+                    State::Ground
+                }
+                (State::Ground, 0x0f) => {
+                    self.is_shifted = false;
+                    State::Ground
+                }
+                (State::Ground, 0x5f..=0x7e) if self.graphics[usize::from(self.is_shifted)] => {
+                    let c = SPECIAL_GRAPHICS[usize::from(byte - 0x5f)];
+                    output.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                    continue;
+                }
+                (State::Ground, _) => State::Ground,
+                (State::Escape, b'(') => State::Designate(0),
+                (State::Escape, b')') => State::Designate(1),
+                (State::Escape, b'[') => State::Csi,
+                (State::Escape, b']' | b'P' | b'X' | b'^' | b'_') => State::String,
+                (State::Escape, _) => State::Ground,
+                (State::Designate(set), _) => {
+                    self.graphics[set] = byte == b'0';
+                    State::Ground
+                }
+                (State::Csi, 0x40..=0x7e) => State::Ground,
+                (State::Csi, _) => State::Csi,
+                (State::String, 0x07) => State::Ground,
+                (State::String, 0x1b) => State::StringEscape,
+                (State::String, _) => State::String,
+                (State::StringEscape, _) => State::Ground,
+            };
+            output.push(byte);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charset() {
+        let mut charset = Charset::default();
+        assert_eq!(
+            "\x1b(0┌──┐\x1b[1m│\x1b(B\x1b[0mlqk".as_bytes(),
+            charset.translate(b"\x1b(0lqqk\x1b[1mx\x1b(B\x1b[0mlqk")
+        );
+        assert_eq!(
+            "\x1b)0a\x0e▒\x0fa\x1b]0;lqk\x07".as_bytes(),
+            charset.translate(b"\x1b)0a\x0ea\x0fa\x1b]0;lqk\x07")
+        );
+        // designation split between the chunks
+        assert_eq!(b"\x1b(".as_slice(), charset.translate(b"\x1b("));
+        assert_eq!("0│".as_bytes(), charset.translate(b"0x"));
+    }
+}
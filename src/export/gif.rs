@@ -0,0 +1,418 @@
+//! Animated GIF rendering of the terminal screen.
+
+use std::{borrow::Cow, collections::HashMap, io::Write, time::Duration};
+
+use color_eyre::Result;
+use embedded_graphics::{
+    image::GetPixel,
+    mono_font::{
+        iso_8859_1::{FONT_8X13, FONT_8X13_BOLD},
+        MonoFont,
+    },
+    pixelcolor::BinaryColor,
+    prelude::{OriginDimensions, Point},
+};
+use gif::{DisposalMethod, Encoder, Repeat};
+
+use super::terminal::{Cell, Rgb, Terminal, PALETTE};
+use crate::pacing::Frame;
+
+/// Width of a cell in pixels.
+const CELL_WIDTH: usize = 8;
+
+/// Height of a cell in pixels.
+const CELL_HEIGHT: usize = 13;
+
+/// Minimum delay between the frames in centiseconds.
+///
+/// Shorter delays are not honored by most of the viewers.
+const MIN_DELAY: u64 = 2;
+
+/// Area of the changed cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Area {
+    /// First row.
+    top: usize,
+    /// First column.
+    left: usize,
+    /// Row after the last one.
+    bottom: usize,
+    /// Column after the last one.
+    right: usize,
+}
+
+/// Indexed pixels of the terminal screen.
+struct Canvas {
+    /// Number of columns.
+    cols: usize,
+    /// Pixels as palette indices.
+    pixels: Vec<u8>,
+    /// Cells that are drawn.
+    cells: Vec<Option<Cell>>,
+    /// Cache of the glyph masks.
+    glyphs: HashMap<(char, bool), Vec<bool>>,
+    /// Cache of the palette indices of the colors.
+    colors: HashMap<Rgb, u8>,
+}
+
+impl Canvas {
+    /// Constructs a new instance of [`Canvas`].
+    fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            cols,
+            pixels: vec![0; rows * cols * CELL_WIDTH * CELL_HEIGHT],
+            cells: vec![None; rows * cols],
+            glyphs: HashMap::new(),
+            colors: HashMap::new(),
+        }
+    }
+
+    /// Returns the width in pixels.
+    fn width(&self) -> usize {
+        self.cols * CELL_WIDTH
+    }
+
+    /// Draws the changed cells of the given terminal.
+    ///
+    /// Returns the area of the changed cells, if any.
+    fn draw(&mut self, terminal: &Terminal) -> Option<Area> {
+        let mut area: Option<Area> = None;
+        for i in 0..self.cells.len() {
+            let (row, col) = (i / self.cols, i % self.cols);
+            let cell = terminal.cell(row as u16, col as u16);
+            if self.cells[i].as_ref() == Some(&cell) {
+                continue;
+            }
+            self.draw_cell(row, col, &cell);
+            self.cells[i] = Some(cell);
+            area = Some(match area {
+                Some(area) => Area {
+                    top: area.top.min(row),
+                    left: area.left.min(col),
+                    bottom: area.bottom.max(row + 1),
+                    right: area.right.max(col + 1),
+                },
+                None => Area {
+                    top: row,
+                    left: col,
+                    bottom: row + 1,
+                    right: col + 1,
+                },
+            });
+        }
+        area
+    }
+
+    /// Returns the palette index of the given color.
+    fn color(&mut self, color: Rgb) -> u8 {
+        *self
+            .colors
+            .entry(color)
+            .or_insert_with(|| nearest_color(color))
+    }
+
+    /// Draws the given cell.
+    fn draw_cell(&mut self, row: usize, col: usize, cell: &Cell) {
+        let width = self.width();
+        let (fg, bg) = (self.color(cell.style.fg), self.color(cell.style.bg));
+        let mask = self
+            .glyphs
+            .entry((cell.c, cell.style.bold))
+            .or_insert_with(|| glyph(cell.c, cell.style.bold));
+        for y in 0..CELL_HEIGHT {
+            let start = (row * CELL_HEIGHT + y) * width + col * CELL_WIDTH;
+            for x in 0..CELL_WIDTH {
+                let is_set = mask[y * CELL_WIDTH + x]
+                    || (cell.style.underline && y == FONT_8X13.underline.offset as usize);
+                self.pixels[start + x] = if is_set { fg } else { bg };
+            }
+        }
+    }
+
+    /// Returns a frame of the given area.
+    fn frame(&self, area: Area) -> gif::Frame<'static> {
+        let width = self.width();
+        let (left, top) = (area.left * CELL_WIDTH, area.top * CELL_HEIGHT);
+        let (right, bottom) = (area.right * CELL_WIDTH, area.bottom * CELL_HEIGHT);
+        let buffer = (top..bottom)
+            .flat_map(|y| &self.pixels[y * width + left..y * width + right])
+            .copied()
+            .collect::<Vec<_>>();
+        gif::Frame {
+            left: left as u16,
+            top: top as u16,
+            width: (right - left) as u16,
+            height: (bottom - top) as u16,
+            dispose: DisposalMethod::Keep,
+            buffer: Cow::Owned(buffer),
+            ..gif::Frame::default()
+        }
+    }
+}
+
+/// Returns the mask of the glyph for the given character.
+fn glyph(c: char, bold: bool) -> Vec<bool> {
+    let mut mask = vec![false; CELL_WIDTH * CELL_HEIGHT];
+    let mut set = |x: usize, y: usize| mask[y * CELL_WIDTH + x] = true;
+    let (cx, cy) = (CELL_WIDTH / 2 - 1, CELL_HEIGHT / 2);
+    if let Some(arms) = box_arms(c) {
+        // up, right, down, left
+        for (arm, weight) in arms.into_iter().enumerate() {
+            let offsets: &[isize] = match weight {
+                1 => &[0],
+                2 => &[0, 1],
+                3 => &[-1, 1],
+                _ => continue,
+            };
+            for offset in offsets {
+                match arm {
+                    0 | 2 => {
+                        let x = cx.saturating_add_signed(*offset);
+                        let range = if arm == 0 { 0..cy + 1 } else { cy..CELL_HEIGHT };
+                        range.for_each(|y| set(x, y));
+                    }
+                    _ => {
+                        let y = cy.saturating_add_signed(*offset);
+                        let range = if arm == 3 { 0..cx + 1 } else { cx..CELL_WIDTH };
+                        range.for_each(|x| set(x, y));
+                    }
+                }
+            }
+        }
+        return mask;
+    }
+    let shape: Option<fn(usize, usize) -> bool> = match c {
+        '█' => Some(|_, _| true),
+        '▀' => Some(|_, y| y < CELL_HEIGHT / 2),
+        '▄' => Some(|_, y| y >= CELL_HEIGHT / 2),
+        '▌' => Some(|x, _| x < CELL_WIDTH / 2),
+        '▐' => Some(|x, _| x >= CELL_WIDTH / 2),
+        '░' => Some(|x, y| x % 2 == 0 && y % 2 == 0),
+        '▒' => Some(|x, y| (x + y) % 2 == 0),
+        '▓' => Some(|x, y| x % 2 == 1 || y % 2 == 1),
+        '◆' => Some(|x, y| x.abs_diff(CELL_WIDTH / 2 - 1) + y.abs_diff(CELL_HEIGHT / 2) <= 3),
+        '⎺' => Some(|_, y| y == 0),
+        '⎻' => Some(|_, y| y == CELL_HEIGHT / 4),
+        '⎼' => Some(|_, y| y == CELL_HEIGHT * 3 / 4),
+        '⎽' => Some(|_, y| y == CELL_HEIGHT - 1),
+        _ => None,
+    };
+    if let Some(shape) = shape {
+        for y in 0..CELL_HEIGHT {
+            for x in 0..CELL_WIDTH {
+                if shape(x, y) {
+                    set(x, y);
+                }
+            }
+        }
+        return mask;
+    }
+    let font: &MonoFont = if bold { &FONT_8X13_BOLD } else { &FONT_8X13 };
+    let columns = font.image.size().width as usize / CELL_WIDTH;
+    let index = font.glyph_mapping.index(c);
+    let (left, top) = (
+        (index % columns) * CELL_WIDTH,
+        (index / columns) * CELL_HEIGHT,
+    );
+    for y in 0..CELL_HEIGHT {
+        for x in 0..CELL_WIDTH {
+            let point = Point::new((left + x) as i32, (top + y) as i32);
+            if font.image.pixel(point) == Some(BinaryColor::On) {
+                set(x, y);
+            }
+        }
+    }
+    mask
+}
+
+/// Returns the weights of the box drawing lines (up, right, down, left) of the given character.
+///
+/// The weights are 1 for light, 2 for heavy and 3 for double lines.
+fn box_arms(c: char) -> Option<[u8; 4]> {
+    Some(match c {
+        '─' => [0, 1, 0, 1],
+        '│' => [1, 0, 1, 0],
+        '┌' => [0, 1, 1, 0],
+        '┐' => [0, 0, 1, 1],
+        '└' => [1, 1, 0, 0],
+        '┘' => [1, 0, 0, 1],
+        '├' => [1, 1, 1, 0],
+        '┤' => [1, 0, 1, 1],
+        '┬' => [0, 1, 1, 1],
+        '┴' => [1, 1, 0, 1],
+        '┼' => [1, 1, 1, 1],
+        '━' => [0, 2, 0, 2],
+        '┃' => [2, 0, 2, 0],
+        '┏' => [0, 2, 2, 0],
+        '┓' => [0, 0, 2, 2],
+        '┗' => [2, 2, 0, 0],
+        '┛' => [2, 0, 0, 2],
+        '┣' => [2, 2, 2, 0],
+        '┫' => [2, 0, 2, 2],
+        '┳' => [0, 2, 2, 2],
+        '┻' => [2, 2, 0, 2],
+        '╋' => [2, 2, 2, 2],
+        '═' => [0, 3, 0, 3],
+        '║' => [3, 0, 3, 0],
+        '╒' => [0, 3, 1, 0],
+        '╓' => [0, 1, 3, 0],
+        '╔' => [0, 3, 3, 0],
+        '╕' => [0, 0, 1, 3],
+        '╖' => [0, 0, 3, 1],
+        '╗' => [0, 0, 3, 3],
+        '╘' => [1, 3, 0, 0],
+        '╙' => [3, 1, 0, 0],
+        '╚' => [3, 3, 0, 0],
+        '╛' => [1, 0, 0, 3],
+        '╜' => [3, 0, 0, 1],
+        '╝' => [3, 0, 0, 3],
+        '╞' => [1, 3, 1, 0],
+        '╟' => [3, 1, 3, 0],
+        '╠' => [3, 3, 3, 0],
+        '╡' => [1, 0, 1, 3],
+        '╢' => [3, 0, 3, 1],
+        '╣' => [3, 0, 3, 3],
+        '╤' => [0, 3, 1, 3],
+        '╥' => [0, 1, 3, 1],
+        '╦' => [0, 3, 3, 3],
+        '╧' => [1, 3, 0, 3],
+        '╨' => [3, 1, 0, 1],
+        '╩' => [3, 3, 0, 3],
+        '╪' => [1, 3, 1, 3],
+        '╫' => [3, 1, 3, 1],
+        '╬' => [3, 3, 3, 3],
+        _ => return None,
+    })
+}
+
+/// Returns the palette index of the color that is nearest to the given one.
+fn nearest_color(color: Rgb) -> u8 {
+    PALETTE
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, other)| {
+            other
+                .iter()
+                .zip(color)
+                .map(|(a, b)| (i32::from(*a) - i32::from(b)).pow(2))
+                .sum::<i32>()
+        })
+        .map(|(i, _)| i as u8)
+        .unwrap_or_default()
+}
+
+/// Returns the given duration in centiseconds.
+fn centis(duration: Duration) -> u64 {
+    (duration.as_millis() / 10) as u64
+}
+
+/// Renders the given terminal output as an animated GIF.
+///
+/// The frames that follow each other quicker than the viewers can show are merged and
+/// the final frame is held for the given duration before the animation loops.
+pub fn encode<W: Write>(
+    writer: W,
+    data: &[u8],
+    frames: &[Frame],
+    size: (u16, u16),
+    hold: Duration,
+) -> Result<()> {
+    let (rows, cols) = size;
+    let mut terminal = Terminal::new(rows, cols);
+    let mut canvas = Canvas::new(usize::from(rows), usize::from(cols));
+    let palette = PALETTE.concat();
+    let mut encoder = Encoder::new(
+        writer,
+        canvas.width() as u16,
+        (usize::from(rows) * CELL_HEIGHT) as u16,
+        &palette,
+    )?;
+    encoder.set_repeat(Repeat::Infinite)?;
+    let mut pending: Option<(gif::Frame, u64)> = None;
+    let mut start = 0;
+    for (i, frame) in frames.iter().enumerate() {
+        if let Some(chunk) = data.get(start..frame.end) {
+            terminal.process(chunk);
+            start = frame.end;
+        }
+        let time = centis(frame.time);
+        let is_last = i + 1 == frames.len();
+        if !is_last && pending.as_ref().is_some_and(|(_, t)| time < t + MIN_DELAY) {
+            continue;
+        }
+        let Some(area) = canvas.draw(&terminal) else {
+            continue;
+        };
+        if let Some((mut frame, t)) = pending.take() {
+            frame.delay = time.saturating_sub(t).clamp(MIN_DELAY, u16::MAX.into()) as u16;
+            encoder.write_frame(&frame)?;
+        }
+        pending = Some((canvas.frame(area), time));
+    }
+    let mut frame = match pending {
+        Some((frame, _)) => frame,
+        None => {
+            let area = canvas.draw(&terminal);
+            canvas.frame(area.unwrap_or(Area {
+                top: 0,
+                left: 0,
+                bottom: usize::from(rows),
+                right: usize::from(cols),
+            }))
+        }
+    };
+    frame.delay = centis(hold).clamp(MIN_DELAY, u16::MAX.into()) as u16;
+    encoder.write_frame(&frame)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gif() -> Result<()> {
+        let data = b"\x1b[1mhello\r\n\x1b(0lqk";
+        let frames = [
+            Frame {
+                time: Duration::ZERO,
+                end: 11,
+            },
+            // merged with the previous frame
+            Frame {
+                time: Duration::from_millis(10),
+                end: 14,
+            },
+            Frame {
+                time: Duration::from_millis(500),
+                end: data.len(),
+            },
+        ];
+        let mut output = Vec::new();
+        encode(&mut output, data, &frames, (2, 10), Duration::from_secs(1))?;
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(output.as_slice())?;
+        assert_eq!((80, 26), (decoder.width(), decoder.height()));
+        let mut delays = Vec::new();
+        let mut areas = Vec::new();
+        while let Some(frame) = decoder.read_next_frame()? {
+            delays.push(frame.delay);
+            areas.push((frame.left, frame.top, frame.width, frame.height));
+        }
+        assert_eq!(vec![50, 100], delays);
+        assert_eq!(vec![(0, 0, 80, 26), (0, 13, 24, 13)], areas);
+        Ok(())
+    }
+
+    #[test]
+    fn test_glyph() {
+        let line = glyph('─', false);
+        assert!((0..CELL_WIDTH).all(|x| line[CELL_HEIGHT / 2 * CELL_WIDTH + x]));
+        assert_eq!(CELL_WIDTH, line.iter().filter(|v| **v).count());
+        assert!(glyph('A', false).iter().any(|v| *v));
+        assert!(!glyph(' ', true).iter().any(|v| *v));
+        assert_eq!(9, nearest_color([0xfe, 0, 1]));
+        assert_eq!(244, nearest_color([0x80, 0x80, 0x80]));
+    }
+}
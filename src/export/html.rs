@@ -0,0 +1,195 @@
+//! Self-contained HTML player.
+
+use std::{io::Write, time::Duration};
+
+use color_eyre::Result;
+
+use super::terminal::{escape, hex, Run, Style, Terminal};
+use crate::pacing::Frame;
+
+/// Template of the HTML player.
+///
+/// The frames are stored as the rows that are changed since the previous frame.
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body { margin: 0; min-height: 100vh; display: flex; flex-direction: column; align-items: center; justify-content: center; gap: 8px; background: #1e1e1e; }
+pre { margin: 0; padding: 8px; color: {fg}; background: {bg}; font: 13px/16px monospace; cursor: pointer; }
+pre div { height: 16px; white-space: pre; }
+input { width: {width}ch; }
+</style>
+</head>
+<body>
+<pre id="screen" title="Click to pause"></pre>
+<input id="progress" type="range" min="0" max="{duration}" value="0">
+<script>
+const rows = {rows};
+const hold = {hold};
+const frames = {frames};
+const screen = document.getElementById("screen");
+const progress = document.getElementById("progress");
+const lines = Array.from({ length: rows }, () => screen.appendChild(document.createElement("div")));
+const duration = frames.length ? frames[frames.length - 1][0] : 0;
+let index = 0, time = 0, paused = false, last = performance.now();
+function apply() {
+  while (index < frames.length && frames[index][0] <= time) {
+    for (const [row, html] of frames[index][1]) lines[row].innerHTML = html;
+    index++;
+  }
+  progress.value = Math.min(time, duration);
+}
+function seek(target) {
+  lines.forEach((line) => (line.innerHTML = ""));
+  index = 0;
+  time = target;
+  apply();
+}
+function tick(now) {
+  if (!paused) {
+    time += now - last;
+    if (time > duration + hold) seek(0);
+    else apply();
+  }
+  last = now;
+  requestAnimationFrame(tick);
+}
+screen.addEventListener("click", () => (paused = !paused));
+progress.addEventListener("input", () => seek(Number(progress.value)));
+requestAnimationFrame(tick);
+</script>
+</body>
+</html>
+"#;
+
+/// Renders the given terminal output as a self-contained HTML file with a player.
+///
+/// The final frame is held for the given duration before the animation loops.
+pub fn encode<W: Write>(
+    mut writer: W,
+    data: &[u8],
+    frames: &[Frame],
+    size: (u16, u16),
+    title: &str,
+    hold: Duration,
+) -> Result<()> {
+    let (rows, cols) = size;
+    let mut terminal = Terminal::new(rows, cols);
+    let mut lines = vec![String::new(); usize::from(rows)];
+    let mut changes = Vec::new();
+    let mut start = 0;
+    for frame in frames {
+        if let Some(chunk) = data.get(start..frame.end) {
+            terminal.process(chunk);
+            start = frame.end;
+        }
+        let mut rows = Vec::new();
+        for (row, line) in lines.iter_mut().enumerate() {
+            let html = render_row(&terminal.runs(row as u16));
+            if *line != html {
+                rows.push((row, html.clone()));
+                *line = html;
+            }
+        }
+        if !rows.is_empty() {
+            changes.push((frame.time.as_millis() as u64, rows));
+        }
+    }
+    let default = Style::default();
+    let html = TEMPLATE
+        .replace("{title}", &escape(title))
+        .replace("{fg}", &hex(default.fg))
+        .replace("{bg}", &hex(default.bg))
+        .replace("{width}", &cols.to_string())
+        .replace(
+            "{duration}",
+            &changes
+                .last()
+                .map(|(time, _)| *time)
+                .unwrap_or_default()
+                .to_string(),
+        )
+        .replace("{rows}", &rows.to_string())
+        .replace("{hold}", &hold.as_millis().to_string())
+        .replace(
+            "{frames}",
+            // the closing tags are escaped to keep the script intact
+            &serde_json::to_string(&changes)?.replace("</", "<\\/"),
+        );
+    writer.write_all(html.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Renders the given runs of a row as HTML.
+fn render_row(runs: &[Run]) -> String {
+    let default = Style::default();
+    let mut html = String::new();
+    for run in runs {
+        let mut css = Vec::new();
+        if run.style.fg != default.fg {
+            css.push(format!("color:{}", hex(run.style.fg)));
+        }
+        if run.style.bg != default.bg {
+            css.push(format!("background:{}", hex(run.style.bg)));
+        }
+        if run.style.bold {
+            css.push(String::from("font-weight:bold"));
+        }
+        if run.style.italic {
+            css.push(String::from("font-style:italic"));
+        }
+        if run.style.underline {
+            css.push(String::from("text-decoration:underline"));
+        }
+        if css.is_empty() {
+            html.push_str(&escape(&run.text));
+        } else {
+            html.push_str(&format!(
+                r#"<span style="{}">{}</span>"#,
+                css.join(";"),
+                escape(&run.text)
+            ));
+        }
+    }
+    html.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html() -> Result<()> {
+        let data = b"a\r\n\x1b[31m</script>";
+        let frames = [
+            Frame {
+                time: Duration::ZERO,
+                end: 3,
+            },
+            Frame {
+                time: Duration::from_millis(250),
+                end: data.len(),
+            },
+        ];
+        let mut output = Vec::new();
+        encode(
+            &mut output,
+            data,
+            &frames,
+            (2, 10),
+            "<test>",
+            Duration::from_secs(1),
+        )?;
+        let output = String::from_utf8_lossy(&output);
+        assert!(output.contains("<title>&lt;test&gt;</title>"));
+        assert!(output.contains("const rows = 2;"));
+        assert!(output.contains("const hold = 1000;"));
+        assert!(output.contains(
+            r#"const frames = [[0,[[0,"a"]]],[250,[[1,"<span style=\"color:#cd0000\">&lt;/script&gt;<\/span>"]]]];"#
+        ));
+        Ok(())
+    }
+}
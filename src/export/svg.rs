@@ -0,0 +1,144 @@
+//! SVG rendering of a single frame.
+
+use std::{fmt::Write as _, io::Write};
+
+use color_eyre::Result;
+
+use super::terminal::{escape, hex, Style, Terminal};
+use crate::pacing::Frame;
+
+/// Width of a cell in pixels.
+const CELL_WIDTH: u32 = 8;
+
+/// Height of a cell in pixels.
+const CELL_HEIGHT: u32 = 16;
+
+/// Font size in pixels.
+const FONT_SIZE: u32 = 13;
+
+/// Distance of the text baseline from the top of the cell in pixels.
+const BASELINE: u32 = 12;
+
+/// Renders the frame at the given position (from 0 to 1) of the terminal output as SVG.
+///
+/// The final frame is rendered if the position is not given.
+pub fn encode<W: Write>(
+    mut writer: W,
+    data: &[u8],
+    frames: &[Frame],
+    size: (u16, u16),
+    at: Option<f64>,
+) -> Result<()> {
+    let end = match at {
+        Some(ratio) => {
+            let time = frames
+                .last()
+                .map(|frame| frame.time.mul_f64(ratio))
+                .unwrap_or_default();
+            let index = frames.partition_point(|frame| frame.time <= time);
+            index.checked_sub(1).map(|i| frames[i].end).unwrap_or(0)
+        }
+        None => data.len(),
+    };
+    let mut terminal = Terminal::new(size.0, size.1);
+    terminal.process(&data[..end.min(data.len())]);
+    writer.write_all(render(&terminal).as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Renders the screen of the given terminal as SVG.
+pub fn render(terminal: &Terminal) -> String {
+    let (rows, cols) = terminal.size();
+    let (width, height) = (u32::from(cols) * CELL_WIDTH, u32::from(rows) * CELL_HEIGHT);
+    let default = Style::default();
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="{FONT_SIZE}px" xml:space="preserve">"#
+    );
+    let _ = write!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+        hex(default.bg)
+    );
+    let runs = (0..rows).map(|row| terminal.runs(row)).collect::<Vec<_>>();
+    for (row, runs) in runs.iter().enumerate() {
+        let y = row as u32 * CELL_HEIGHT;
+        for run in runs.iter().filter(|run| run.style.bg != default.bg) {
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{y}" width="{}" height="{CELL_HEIGHT}" fill="{}"/>"#,
+                u32::from(run.col) * CELL_WIDTH,
+                u32::from(run.width) * CELL_WIDTH,
+                hex(run.style.bg)
+            );
+        }
+    }
+    for (row, runs) in runs.iter().enumerate() {
+        let mut text = String::new();
+        for run in runs
+            .iter()
+            .filter(|run| run.style.underline || !run.text.trim().is_empty())
+        {
+            let _ = write!(
+                text,
+                r#"<tspan x="{}" textLength="{}" fill="{}""#,
+                u32::from(run.col) * CELL_WIDTH,
+                u32::from(run.width) * CELL_WIDTH,
+                hex(run.style.fg)
+            );
+            if run.style.bold {
+                text.push_str(r#" font-weight="bold""#);
+            }
+            if run.style.italic {
+                text.push_str(r#" font-style="italic""#);
+            }
+            if run.style.underline {
+                text.push_str(r#" text-decoration="underline""#);
+            }
+            let _ = write!(text, ">{}</tspan>", escape(&run.text));
+        }
+        if !text.is_empty() {
+            let _ = write!(
+                svg,
+                r#"<text y="{}">{text}</text>"#,
+                row as u32 * CELL_HEIGHT + BASELINE
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_svg() -> Result<()> {
+        let data = b"a<b\r\n\x1b[1;4;44mc";
+        let frames = [
+            Frame {
+                time: Duration::ZERO,
+                end: 5,
+            },
+            Frame {
+                time: Duration::from_secs(1),
+                end: data.len(),
+            },
+        ];
+        let mut output = Vec::new();
+        encode(&mut output, data, &frames, (2, 4), Some(0.5))?;
+        assert_eq!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="32" height="32" viewBox="0 0 32 32" font-family="monospace" font-size="13px" xml:space="preserve"><rect width="32" height="32" fill="#000000"/><text y="12"><tspan x="0" textLength="32" fill="#e5e5e5">a&lt;b </tspan></text></svg>
+"##,
+            String::from_utf8_lossy(&output)
+        );
+        let mut output = Vec::new();
+        encode(&mut output, data, &frames, (2, 4), None)?;
+        let output = String::from_utf8_lossy(&output);
+        assert!(output.contains(r##"<rect x="0" y="16" width="8" height="16" fill="#0000ee"/>"##));
+        assert!(output.contains(r##"<tspan x="0" textLength="8" fill="#ffffff" font-weight="bold" text-decoration="underline">c</tspan>"##));
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//! Terminal emulation for rendering the exported frames.
+
+use super::charset::Charset;
+
+/// RGB color.
+pub type Rgb = [u8; 3];
+
+/// xterm 256 color palette.
+pub const PALETTE: [Rgb; 256] = palette();
+
+/// Palette index of the default foreground color.
+const DEFAULT_FG: u8 = 7;
+
+/// Palette index of the default background color.
+const DEFAULT_BG: u8 = 0;
+
+/// Returns the xterm 256 color palette.
+const fn palette() -> [Rgb; 256] {
+    const BASE: [Rgb; 16] = [
+        [0x00, 0x00, 0x00],
+        [0xcd, 0x00, 0x00],
+        [0x00, 0xcd, 0x00],
+        [0xcd, 0xcd, 0x00],
+        [0x00, 0x00, 0xee],
+        [0xcd, 0x00, 0xcd],
+        [0x00, 0xcd, 0xcd],
+        [0xe5, 0xe5, 0xe5],
+        [0x7f, 0x7f, 0x7f],
+        [0xff, 0x00, 0x00],
+        [0x00, 0xff, 0x00],
+        [0xff, 0xff, 0x00],
+        [0x5c, 0x5c, 0xff],
+        [0xff, 0x00, 0xff],
+        [0x00, 0xff, 0xff],
+        [0xff, 0xff, 0xff],
+    ];
+    const LEVELS: [u8; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+    let mut palette = [[0; 3]; 256];
+    let mut i = 0;
+    while i < 16 {
+        palette[i] = BASE[i];
+        i += 1;
+    }
+    while i < 232 {
+        let n = i - 16;
+        palette[i] = [LEVELS[n / 36], LEVELS[n / 6 % 6], LEVELS[n % 6]];
+        i += 1;
+    }
+    while i < 256 {
+        palette[i] = [(8 + (i - 232) * 10) as u8; 3];
+        i += 1;
+    }
+    palette
+}
+
+/// Style of a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    /// Foreground color.
+    pub fg: Rgb,
+    /// Background color.
+    pub bg: Rgb,
+    /// Is the text bold?
+    pub bold: bool,
+    /// Is the text italic?
+    pub italic: bool,
+    /// Is the text underlined?
+    pub underline: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fg: PALETTE[usize::from(DEFAULT_FG)],
+            bg: PALETTE[usize::from(DEFAULT_BG)],
+            bold: false,
+            italic: false,
+            underline: false,
+        }
+    }
+}
+
+/// Character and style of a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    /// Character in the cell.
+    pub c: char,
+    /// Style of the cell.
+    pub style: Style,
+}
+
+/// Run of the cells with the same style in a row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    /// First column.
+    pub col: u16,
+    /// Number of columns.
+    pub width: u16,
+    /// Text of the cells.
+    pub text: String,
+    /// Style of the cells.
+    pub style: Style,
+}
+
+/// Terminal that the exported frames are rendered from.
+///
+/// Wraps the same [`vt100::Parser`] that is used for playing the animations.
+pub struct Terminal {
+    /// Terminal parser.
+    parser: vt100::Parser,
+    /// DEC special graphics translator.
+    charset: Charset,
+}
+
+impl Terminal {
+    /// Constructs a new instance of [`Terminal`] with the given size.
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            parser: vt100::Parser::new(rows, cols, 0),
+            charset: Charset::default(),
+        }
+    }
+
+    /// Processes the given output.
+    pub fn process(&mut self, data: &[u8]) {
+        let data = self.charset.translate(data);
+        self.parser.process(&data);
+    }
+
+    /// Returns the screen.
+    pub fn screen(&self) -> &vt100::Screen {
+        self.parser.screen()
+    }
+
+    /// Returns the size as rows and columns.
+    pub fn size(&self) -> (u16, u16) {
+        self.parser.screen().size()
+    }
+
+    /// Returns the cell at the given position.
+    pub fn cell(&self, row: u16, col: u16) -> Cell {
+        let Some(cell) = self.parser.screen().cell(row, col) else {
+            return Cell {
+                c: ' ',
+                style: Style::default(),
+            };
+        };
+        let index = |color, default| match color {
+            vt100::Color::Default => Ok(default),
+            vt100::Color::Idx(i) => Ok(i),
+            vt100::Color::Rgb(r, g, b) => Err([r, g, b]),
+        };
+        let fg = match index(cell.fgcolor(), DEFAULT_FG) {
+            Ok(i) if cell.bold() && i < 8 => PALETTE[usize::from(i + 8)],
+            Ok(i) => PALETTE[usize::from(i)],
+            Err(rgb) => rgb,
+        };
+        let bg = match index(cell.bgcolor(), DEFAULT_BG) {
+            Ok(i) => PALETTE[usize::from(i)],
+            Err(rgb) => rgb,
+        };
+        let (fg, bg) = if cell.inverse() { (bg, fg) } else { (fg, bg) };
+        Cell {
+            c: cell.contents().chars().next().unwrap_or(' '),
+            style: Style {
+                fg,
+                bg,
+                bold: cell.bold(),
+                italic: cell.italic(),
+                underline: cell.underline(),
+            },
+        }
+    }
+
+    /// Returns the runs of the cells with the same style in the given row.
+    pub fn runs(&self, row: u16) -> Vec<Run> {
+        let (_, cols) = self.size();
+        let mut runs: Vec<Run> = Vec::new();
+        for col in 0..cols {
+            let is_continuation = self
+                .parser
+                .screen()
+                .cell(row, col)
+                .is_some_and(|cell| cell.is_wide_continuation());
+            let cell = self.cell(row, col);
+            match runs.last_mut() {
+                Some(run) if run.style == cell.style || is_continuation => {
+                    run.width += 1;
+                    if !is_continuation {
+                        run.text.push(cell.c);
+                    }
+                }
+                _ => runs.push(Run {
+                    col,
+                    width: 1,
+                    text: cell.c.to_string(),
+                    style: cell.style,
+                }),
+            }
+        }
+        runs
+    }
+}
+
+/// Returns the given color in hexadecimal notation.
+pub fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// Escapes the given text for XML and HTML.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminal() {
+        let mut terminal = Terminal::new(2, 8);
+        terminal.process(b"ab\x1b[1;31mcd\x1b[0;7me\x1b(0q");
+        assert_eq!([0xff, 0x00, 0x00], terminal.cell(0, 2).style.fg);
+        assert_eq!(PALETTE[7], terminal.cell(0, 4).style.bg);
+        assert_eq!('─', terminal.cell(0, 5).c);
+        let runs = terminal.runs(0);
+        assert_eq!(
+            vec![(0, "ab"), (2, "cd"), (4, "e─"), (6, "  ")],
+            runs.iter()
+                .map(|run| (run.col, run.text.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("#5c5cff", hex(PALETTE[12]));
+        assert_eq!("&lt;a href=&quot;&amp;&quot;&gt;", escape("<a href=\"&\">"));
+        assert_eq!([0x80; 3], PALETTE[244]);
+        assert_eq!([0x87, 0xaf, 0xd7], PALETTE[16 + 2 * 36 + 3 * 6 + 4]);
+    }
+}
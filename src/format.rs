@@ -0,0 +1,107 @@
+use std::{fs, path::Path};
+
+use color_eyre::{eyre::eyre, Result};
+
+use crate::pacing::Frame;
+use encoding::Encoding;
+use sauce::Sauce;
+
+pub mod asciicast;
+pub mod encoding;
+pub mod sauce;
+pub mod timing;
+pub mod ttyrec;
+
+/// Decoded animation file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Recording {
+    /// Terminal output.
+    pub data: Vec<u8>,
+    /// Recorded frames, if the format has timing information.
+    pub frames: Option<Vec<Frame>>,
+    /// Terminal size as rows and columns, if the format has it.
+    pub size: Option<(u16, u16)>,
+    /// SAUCE metadata, if the file has it.
+    pub sauce: Option<Sauce>,
+}
+
+impl Recording {
+    /// Returns the terminal width in columns, if the format has it.
+    pub fn width(&self) -> Option<u16> {
+        self.size
+            .map(|(_, cols)| cols)
+            .or_else(|| self.sauce.as_ref().and_then(|sauce| sauce.width))
+    }
+}
+
+impl From<Vec<u8>> for Recording {
+    fn from(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            ..Self::default()
+        }
+    }
+}
+
+/// Decodes the given file based on its name and contents.
+///
+/// Files without a known format are returned as raw terminal output, without the
+/// SAUCE metadata if they have it.
+pub fn decode(name: &str, data: Vec<u8>) -> Result<Recording> {
+    if name.ends_with(".cast") || asciicast::detect(&data) {
+        asciicast::decode(&data)
+    } else if let Some((end, sauce)) = sauce::parse(&data) {
+        Ok(Recording {
+            data: data[..end].to_vec(),
+            sauce: Some(sauce),
+            ..Recording::default()
+        })
+    } else if name.ends_with(".ttyrec") || ttyrec::detect(&data) {
+        ttyrec::decode(&data)
+    } else {
+        Ok(Recording::from(data))
+    }
+}
+
+/// Decodes the given typescript with a `script` timing file if it is given,
+/// see [`decode`] otherwise.
+///
+/// The terminal output is transcoded from the given encoding to UTF-8.
+pub fn decode_with_timing(
+    name: &str,
+    data: Vec<u8>,
+    timing: Option<&Path>,
+    encoding: Encoding,
+) -> Result<Recording> {
+    let recording = match timing {
+        Some(path) => timing::decode(
+            &data,
+            &fs::read_to_string(path)
+                .map_err(|e| eyre!("failed to read {}: {e}", path.display()))?,
+        ),
+        None => decode(name, data),
+    }?;
+    Ok(encoding::transcode(recording, encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Animations;
+
+    #[test]
+    fn test_embedded_raw() -> Result<()> {
+        for name in Animations::iter() {
+            let data = Animations::get(&name)
+                .expect("no animation")
+                .data
+                .into_owned();
+            assert_eq!(
+                Recording::from(data.clone()),
+                decode(&name, data)?,
+                "{name}"
+            );
+        }
+        Ok(())
+    }
+}
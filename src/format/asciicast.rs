@@ -0,0 +1,231 @@
+//! [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) format.
+
+use std::{io::Write, time::Duration};
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use super::Recording;
+use crate::pacing::Frame;
+
+/// Header of an asciicast file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Header {
+    /// Format version.
+    version: u8,
+    /// Terminal width.
+    width: u16,
+    /// Terminal height.
+    height: u16,
+    /// Maximum idle time between the events in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idle_time_limit: Option<f64>,
+    /// Title of the recording.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+}
+
+/// Returns whether the given data looks like an asciicast v2 file.
+pub fn detect(data: &[u8]) -> bool {
+    data.split(|b| *b == b'\n')
+        .next()
+        .and_then(|line| serde_json::from_slice::<Header>(line).ok())
+        .is_some_and(|header| header.version == 2)
+}
+
+/// Decodes an asciicast v2 file.
+///
+/// Only the output events are played, gaps between the events are limited by
+/// `idle_time_limit` if it is present in the header.
+pub fn decode(data: &[u8]) -> Result<Recording> {
+    let text = String::from_utf8_lossy(data);
+    let mut lines = text.lines().enumerate();
+    let header = lines
+        .next()
+        .map(|(_, line)| serde_json::from_str::<Header>(line))
+        .ok_or_else(|| eyre!("missing asciicast header"))?
+        .map_err(|e| eyre!("invalid asciicast header: {e}"))?;
+    if header.version != 2 {
+        return Err(eyre!("unsupported asciicast version: {}", header.version));
+    }
+    if header.width == 0 || header.height == 0 {
+        return Err(eyre!(
+            "invalid asciicast size: {}x{}",
+            header.width,
+            header.height
+        ));
+    }
+    if let Some(limit) = header.idle_time_limit.filter(|l| !l.is_finite() || *l < 0.) {
+        return Err(eyre!("invalid asciicast idle time limit: {limit}"));
+    }
+    let idle_time_limit = header.idle_time_limit.unwrap_or(f64::INFINITY);
+    let mut recording = Recording {
+        size: Some((header.height, header.width)),
+        ..Recording::default()
+    };
+    let mut frames = Vec::new();
+    let (mut last_time, mut time) = (0., 0.);
+    for (i, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (event_time, code, output) = serde_json::from_str::<(f64, String, String)>(line)
+            .map_err(|e| eyre!("invalid asciicast event at line {}: {e}", i + 1))?;
+        if code != "o" {
+            continue;
+        }
+        time += (event_time - last_time).clamp(0., idle_time_limit);
+        last_time = event_time;
+        recording.data.extend_from_slice(output.as_bytes());
+        frames.push(Frame {
+            time: Duration::try_from_secs_f64(time)
+                .map_err(|e| eyre!("invalid asciicast event at line {}: {e}", i + 1))?,
+            end: recording.data.len(),
+        });
+    }
+    recording.frames = Some(frames);
+    Ok(recording)
+}
+
+/// Encodes the given terminal output as an asciicast v2 file.
+///
+/// Every frame becomes an output event, the size is given as rows and columns.
+pub fn encode<W: Write>(
+    mut writer: W,
+    data: &[u8],
+    frames: &[Frame],
+    size: (u16, u16),
+    title: Option<&str>,
+) -> Result<()> {
+    let header = Header {
+        version: 2,
+        width: size.1,
+        height: size.0,
+        idle_time_limit: None,
+        title: title.map(String::from),
+    };
+    serde_json::to_writer(&mut writer, &header)?;
+    writeln!(writer)?;
+    let (mut start, mut pending) = (0, Vec::new());
+    for frame in frames {
+        pending.extend_from_slice(data.get(start..frame.end).unwrap_or_default());
+        start = start.max(frame.end);
+        let output = take_utf8(&mut pending);
+        if !output.is_empty() {
+            serde_json::to_writer(&mut writer, &(frame.time.as_secs_f64(), "o", output))?;
+            writeln!(writer)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Takes the longest prefix of the buffer that can be decoded as UTF-8.
+///
+/// Invalid bytes are replaced while an incomplete character at the end is kept.
+fn take_utf8(buffer: &mut Vec<u8>) -> String {
+    let mut output = String::new();
+    let mut rest = buffer.as_slice();
+    while let Err(e) = std::str::from_utf8(rest) {
+        let (valid, invalid) = rest.split_at(e.valid_up_to());
+        output.push_str(&String::from_utf8_lossy(valid));
+        match e.error_len() {
+            Some(len) => {
+                output.push(char::REPLACEMENT_CHARACTER);
+                rest = &invalid[len..];
+            }
+            None => {
+                rest = invalid;
+                break;
+            }
+        }
+    }
+    if std::str::from_utf8(rest).is_ok() {
+        output.push_str(&String::from_utf8_lossy(rest));
+        rest = &[];
+    }
+    let consumed = buffer.len() - rest.len();
+    buffer.drain(..consumed);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciicast() -> Result<()> {
+        let data = br#"{"version": 2, "width": 100, "height": 30, "idle_time_limit": 1.5}
+[0.5, "o", "hello"]
+[0.7, "i", "x"]
+[1.0, "o", " world\r\n"]
+[10.0, "o", "\u001b[1mbye"]
+"#;
+        assert!(detect(data));
+        let recording = decode(data)?;
+        assert_eq!(Some((30, 100)), recording.size);
+        assert_eq!(b"hello world\r\n\x1b[1mbye", recording.data.as_slice());
+        assert_eq!(
+            Some(vec![
+                Frame {
+                    time: Duration::from_millis(500),
+                    end: 5
+                },
+                Frame {
+                    time: Duration::from_millis(1000),
+                    end: 13
+                },
+                Frame {
+                    time: Duration::from_millis(2500),
+                    end: 20
+                },
+            ]),
+            recording.frames
+        );
+        assert!(!detect(b"\x1b[2Jhello"));
+        for data in [
+            r#"{"version": 2, "width": 80, "height": 24, "idle_time_limit": -1}"#,
+            r#"{"version": 2, "width": 0, "height": 0}"#,
+            "{\"version\": 2, \"width\": 80, \"height\": 24}\n[1e300, \"o\", \"x\"]",
+        ] {
+            assert!(decode(data.as_bytes()).is_err(), "{data}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode() -> Result<()> {
+        let data = "hello\r\n\u{2500}\x1b[0m".as_bytes();
+        let frames = [
+            Frame {
+                time: Duration::ZERO,
+                end: 7,
+            },
+            // splits the box drawing character
+            Frame {
+                time: Duration::from_millis(100),
+                end: 8,
+            },
+            Frame {
+                time: Duration::from_millis(200),
+                end: data.len(),
+            },
+        ];
+        let mut output = Vec::new();
+        encode(&mut output, data, &frames, (24, 80), Some("test"))?;
+        assert_eq!(
+            r#"{"version":2,"width":80,"height":24,"title":"test"}
+[0.0,"o","hello\r\n"]
+[0.2,"o","─\u001b[0m"]
+"#,
+            String::from_utf8_lossy(&output)
+        );
+        let recording = decode(&output)?;
+        assert_eq!(data, recording.data.as_slice());
+        assert_eq!(Some((24, 80)), recording.size);
+        let mut buffer = b"a\xffb\xe2\x94".to_vec();
+        assert_eq!("a\u{FFFD}b", take_utf8(&mut buffer));
+        assert_eq!(b"\xe2\x94", buffer.as_slice());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! Character encodings of the art files.
+//!
+//! The output is transcoded to UTF-8 before it is played. Only the bytes from `0x80`
+//! are transcoded so that the control codes and escape sequences are left intact.
+
+use clap::ValueEnum;
+
+use super::Recording;
+use crate::pacing::Frame;
+
+/// Characters of code page 437 for the bytes from `0x80` to `0xff`.
+const CP437: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Character encoding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Encoding {
+    /// Detect the encoding from the contents.
+    #[default]
+    Auto,
+    /// UTF-8.
+    Utf8,
+    /// Code page 437 (IBM PC).
+    Cp437,
+    /// ISO-8859-1.
+    Latin1,
+}
+
+impl Encoding {
+    /// Detects the encoding of the given data.
+    ///
+    /// Files that are not valid UTF-8 are assumed to be CP437 if the bytes are mostly
+    /// in its accented letters, box drawing and block ranges, Latin-1 otherwise.
+    pub fn detect(data: &[u8]) -> Self {
+        if std::str::from_utf8(data).is_ok() {
+            return Self::Utf8;
+        }
+        let (cp437, latin1) = data
+            .iter()
+            .fold((0, 0), |(cp437, latin1), byte| match byte {
+                0x80..=0x9f | 0xb0..=0xdf => (cp437 + 1, latin1),
+                0xa0..=0xaf | 0xe0..=0xff => (cp437, latin1 + 1),
+                _ => (cp437, latin1),
+            });
+        if cp437 >= latin1 {
+            Self::Cp437
+        } else {
+            Self::Latin1
+        }
+    }
+
+    /// Transcodes the given data to UTF-8.
+    ///
+    /// Invalid UTF-8 sequences are left as is since they are handled by the parser.
+    pub fn transcode(self, data: &[u8]) -> Vec<u8> {
+        let map = |byte: u8| match self {
+            Self::Cp437 => CP437[usize::from(byte - 0x80)],
+            _ => char::from(byte),
+        };
+        match self {
+            Self::Auto => Self::detect(data).transcode(data),
+            Self::Utf8 => data.to_vec(),
+            Self::Cp437 | Self::Latin1 => {
+                let mut output = Vec::with_capacity(data.len());
+                for &byte in data {
+                    if byte < 0x80 {
+                        output.push(byte);
+                    } else {
+                        let mut buffer = [0; 4];
+                        output.extend_from_slice(map(byte).encode_utf8(&mut buffer).as_bytes());
+                    }
+                }
+                output
+            }
+        }
+    }
+}
+
+/// Transcodes the given recording to UTF-8.
+///
+/// The encoding is detected once for the whole recording (preferring CP437 for the files
+/// with SAUCE metadata) and the frames are adjusted to the transcoded data.
+pub fn transcode(recording: Recording, encoding: Encoding) -> Recording {
+    let encoding = match (encoding, Encoding::detect(&recording.data)) {
+        // ANSI art with SAUCE metadata is from the PC era
+        (Encoding::Auto, Encoding::Latin1) if recording.sauce.is_some() => Encoding::Cp437,
+        (Encoding::Auto, detected) => detected,
+        (encoding, _) => encoding,
+    };
+    if encoding == Encoding::Utf8 {
+        return recording;
+    }
+    let Some(frames) = recording.frames else {
+        return Recording {
+            data: encoding.transcode(&recording.data),
+            ..recording
+        };
+    };
+    let mut data = Vec::with_capacity(recording.data.len());
+    let mut start = 0;
+    let frames = frames
+        .into_iter()
+        .map(|frame| {
+            let end = frame.end.clamp(start, recording.data.len());
+            data.extend(encoding.transcode(&recording.data[start..end]));
+            start = end;
+            Frame {
+                end: data.len(),
+                ..frame
+            }
+        })
+        .collect();
+    data.extend(encoding.transcode(&recording.data[start..]));
+    Recording {
+        data,
+        frames: Some(frames),
+        ..recording
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(Encoding::Utf8, Encoding::detect("┌─┐".as_bytes()));
+        assert_eq!(Encoding::Cp437, Encoding::detect(b"\x1b[1m\xda\xc4\xbf"));
+        assert_eq!(Encoding::Latin1, Encoding::detect(b"caf\xe9"));
+        assert_eq!(
+            "\x1b[1m┌─┐█".as_bytes(),
+            Encoding::Cp437.transcode(b"\x1b[1m\xda\xc4\xbf\xdb")
+        );
+        assert_eq!("café".as_bytes(), Encoding::Latin1.transcode(b"caf\xe9"));
+        assert_eq!("café".as_bytes(), Encoding::Auto.transcode(b"caf\xe9"));
+        assert_eq!(b"\xff".as_slice(), Encoding::Utf8.transcode(b"\xff"));
+    }
+
+    #[test]
+    fn test_transcode() {
+        let recording = Recording {
+            data: b"\xb0a\xb1".to_vec(),
+            frames: Some(vec![
+                Frame {
+                    time: Duration::ZERO,
+                    end: 2,
+                },
+                Frame {
+                    time: Duration::from_secs(1),
+                    end: 3,
+                },
+            ]),
+            ..Recording::default()
+        };
+        let recording = transcode(recording, Encoding::Auto);
+        assert_eq!("░a▒".as_bytes(), recording.data.as_slice());
+        assert_eq!(
+            Some(vec![4, 7]),
+            recording
+                .frames
+                .map(|frames| frames.iter().map(|frame| frame.end).collect())
+        );
+    }
+}
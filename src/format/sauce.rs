@@ -0,0 +1,129 @@
+//! [SAUCE](https://www.acid.org/info/sauce/sauce.htm) metadata of the ANSI art files.
+
+use std::fmt;
+
+use super::encoding::Encoding;
+
+/// Length of the SAUCE record.
+const RECORD_LEN: usize = 128;
+
+/// Length of a comment line.
+const COMMENT_LEN: usize = 64;
+
+/// End of file character that precedes the metadata.
+const EOF: u8 = 0x1a;
+
+/// Data type of the character based files.
+const DATA_TYPE_CHARACTER: u8 = 1;
+
+/// SAUCE record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sauce {
+    /// Title of the art.
+    pub title: String,
+    /// Author of the art.
+    pub author: String,
+    /// Group of the author.
+    pub group: String,
+    /// Creation date as `CCYYMMDD`.
+    pub date: String,
+    /// Width in columns.
+    pub width: Option<u16>,
+}
+
+impl fmt::Display for Sauce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = if self.title.is_empty() {
+            "Untitled"
+        } else {
+            &self.title
+        };
+        write!(f, "{title}")?;
+        match (self.author.is_empty(), self.group.is_empty()) {
+            (false, false) => write!(f, " by {} of {}", self.author, self.group)?,
+            (false, true) => write!(f, " by {}", self.author)?,
+            (true, false) => write!(f, " by {}", self.group)?,
+            (true, true) => {}
+        }
+        if let [y1, y2, y3, y4, m1, m2, d1, d2] = self.date.as_bytes() {
+            let (year, month, day) = ([*y1, *y2, *y3, *y4], [*m1, *m2], [*d1, *d2]);
+            write!(
+                f,
+                " ({}-{}-{})",
+                String::from_utf8_lossy(&year),
+                String::from_utf8_lossy(&month),
+                String::from_utf8_lossy(&day)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the SAUCE record at the end of the given data.
+///
+/// Returns the length of the contents without the metadata and the record.
+pub fn parse(data: &[u8]) -> Option<(usize, Sauce)> {
+    let mut end = data.len().checked_sub(RECORD_LEN)?;
+    let record = &data[end..];
+    if !record.starts_with(b"SAUCE") {
+        return None;
+    }
+    let comments = usize::from(record[104]);
+    if comments > 0 {
+        if let Some(start) = end.checked_sub(5 + comments * COMMENT_LEN) {
+            if data[start..].starts_with(b"COMNT") {
+                end = start;
+            }
+        }
+    }
+    if end > 0 && data[end - 1] == EOF {
+        end -= 1;
+    }
+    let text = |range: std::ops::Range<usize>| {
+        String::from_utf8_lossy(&Encoding::Cp437.transcode(&record[range]))
+            .trim_end_matches([' ', '\0'])
+            .to_string()
+    };
+    let width = u16::from_le_bytes([record[96], record[97]]);
+    let sauce = Sauce {
+        title: text(7..42),
+        author: text(42..62),
+        group: text(62..82),
+        date: text(82..90),
+        width: (record[94] == DATA_TYPE_CHARACTER && width > 0).then_some(width),
+    };
+    Some((end, sauce))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sauce() {
+        let mut data = b"\x1b[1mhello\x1a".to_vec();
+        data.extend_from_slice(b"COMNT");
+        data.extend_from_slice(&[b' '; COMMENT_LEN]);
+        let mut record = b"SAUCE00".to_vec();
+        record.extend_from_slice(format!("{:<35}{:<20}{:<20}", "Hello", "dude", "").as_bytes());
+        record.extend_from_slice(b"19960415");
+        record.extend_from_slice(&[0; 4]);
+        record.extend_from_slice(&[1, 1, 160, 0, 25, 0, 0, 0, 0, 0, 1, 0]);
+        record.resize(RECORD_LEN, 0);
+        data.extend_from_slice(&record);
+        let (end, sauce) = parse(&data).expect("no sauce");
+        assert_eq!(b"\x1b[1mhello", &data[..end]);
+        assert_eq!(
+            Sauce {
+                title: String::from("Hello"),
+                author: String::from("dude"),
+                group: String::new(),
+                date: String::from("19960415"),
+                width: Some(160),
+            },
+            sauce
+        );
+        assert_eq!("Hello by dude (1996-04-15)", sauce.to_string());
+        assert_eq!(None, parse(b"hello"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Timing files of util-linux [`script`](https://man7.org/linux/man-pages/man1/script.1.html).
+//!
+//! Both the classic (`<delay> <bytes>`) and the advanced multi-stream
+//! (`<type> <delay> <value>`) formats are supported. The typescript is expected to
+//! contain the output stream, so only the output entries consume its bytes.
+
+use std::time::Duration;
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::Recording;
+use crate::pacing::Frame;
+
+/// Prefix of the header line written by `script` to the typescript.
+const HEADER: &[u8] = b"Script started on ";
+
+/// Decodes a typescript with the given timing file.
+pub fn decode(typescript: &[u8], timing: &str) -> Result<Recording> {
+    let mut offset = if typescript.starts_with(HEADER) {
+        typescript
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|i| i + 1)
+            .unwrap_or(typescript.len())
+    } else {
+        0
+    };
+    let mut recording = Recording::default();
+    let mut frames = Vec::new();
+    let mut time = Duration::ZERO;
+    for (i, line) in timing.lines().enumerate() {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let (delay, bytes) = match fields.as_slice() {
+            [] => continue,
+            [delay, bytes] => (delay, Some(bytes)),
+            ["O", delay, bytes, ..] => (delay, Some(bytes)),
+            ["I" | "S" | "H", delay, ..] => (delay, None),
+            _ => return Err(eyre!("invalid timing entry at line {}: {line}", i + 1)),
+        };
+        time = delay
+            .parse::<f64>()
+            .ok()
+            .and_then(|delay| Duration::try_from_secs_f64(delay).ok())
+            .and_then(|delay| time.checked_add(delay))
+            .ok_or_else(|| eyre!("invalid delay at line {}: {delay}", i + 1))?;
+        let Some(bytes) = bytes else {
+            continue;
+        };
+        let bytes = bytes
+            .parse::<usize>()
+            .map_err(|e| eyre!("invalid byte count at line {}: {e}", i + 1))?;
+        let end = offset.saturating_add(bytes).min(typescript.len());
+        recording.data.extend_from_slice(&typescript[offset..end]);
+        offset = end;
+        frames.push(Frame {
+            time,
+            end: recording.data.len(),
+        });
+    }
+    // play the rest (e.g. "Script done" trailer) at the end
+    if offset < typescript.len() {
+        recording.data.extend_from_slice(&typescript[offset..]);
+        frames.push(Frame {
+            time,
+            end: recording.data.len(),
+        });
+    }
+    recording.frames = Some(frames);
+    Ok(recording)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TYPESCRIPT: &[u8] = b"Script started on 2024-10-01 12:00:00+00:00\nhello world\r\n";
+
+    #[test]
+    fn test_classic_timing() -> Result<()> {
+        let recording = decode(TYPESCRIPT, "0.5 5\n1.25 6\n0.1 2\n")?;
+        assert_eq!(b"hello world\r\n", recording.data.as_slice());
+        assert_eq!(
+            Some(vec![
+                Frame {
+                    time: Duration::from_millis(500),
+                    end: 5
+                },
+                Frame {
+                    time: Duration::from_millis(1750),
+                    end: 11
+                },
+                Frame {
+                    time: Duration::from_millis(1850),
+                    end: 13
+                },
+            ]),
+            recording.frames
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_advanced_timing() -> Result<()> {
+        let timing = "H 0 START_TIME 2024-10-01 12:00:00\nO 0.5 5\nI 0.25 1\nS 0.25 SIGWINCH ROWS=24 COLS=80\nO 0.5 8\n";
+        let recording = decode(TYPESCRIPT, timing)?;
+        assert_eq!(b"hello world\r\n", recording.data.as_slice());
+        assert_eq!(
+            Some(vec![
+                Frame {
+                    time: Duration::from_millis(500),
+                    end: 5
+                },
+                Frame {
+                    time: Duration::from_millis(1500),
+                    end: 13
+                },
+            ]),
+            recording.frames
+        );
+        assert!(decode(TYPESCRIPT, "X 1 2").is_err());
+        assert!(decode(TYPESCRIPT, "-1 2").is_err());
+        assert!(decode(TYPESCRIPT, "1e30 3").is_err());
+        assert!(decode(TYPESCRIPT, "1e19 1\n1e19 1").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! [ttyrec](https://en.wikipedia.org/wiki/Ttyrec) format.
+
+use std::time::Duration;
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::Recording;
+use crate::pacing::Frame;
+
+/// Size of a record header: seconds, microseconds and payload length.
+const HEADER_LEN: usize = 12;
+
+/// Record in a ttyrec file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Record<'a> {
+    /// Time of the record since the epoch.
+    time: Duration,
+    /// Terminal output.
+    payload: &'a [u8],
+}
+
+/// Parses the records of a ttyrec file.
+fn records(data: &[u8]) -> Result<Vec<Record<'_>>> {
+    let mut records = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        if rest.len() < HEADER_LEN {
+            return Err(eyre!(
+                "truncated ttyrec header at {}",
+                data.len() - rest.len()
+            ));
+        }
+        let field = |i: usize| u32::from_le_bytes([rest[i], rest[i + 1], rest[i + 2], rest[i + 3]]);
+        let (sec, usec, len) = (field(0), field(4), field(8) as usize);
+        if usec >= 1_000_000 || rest.len() < HEADER_LEN + len {
+            return Err(eyre!(
+                "invalid ttyrec record at {}",
+                data.len() - rest.len()
+            ));
+        }
+        records.push(Record {
+            time: Duration::from_secs(sec.into()) + Duration::from_micros(usec.into()),
+            payload: &rest[HEADER_LEN..HEADER_LEN + len],
+        });
+        rest = &rest[HEADER_LEN + len..];
+    }
+    Ok(records)
+}
+
+/// Returns whether the given data looks like a ttyrec file.
+///
+/// The whole file should consist of valid records with non-decreasing timestamps.
+pub fn detect(data: &[u8]) -> bool {
+    records(data).is_ok_and(|records| {
+        !records.is_empty() && records.windows(2).all(|w| w[0].time <= w[1].time)
+    })
+}
+
+/// Decodes a ttyrec file.
+///
+/// The timestamps are made relative to the first record.
+pub fn decode(data: &[u8]) -> Result<Recording> {
+    let records = records(data)?;
+    let start = records.first().map(|r| r.time).unwrap_or_default();
+    let mut recording = Recording::default();
+    let mut frames = Vec::new();
+    for record in records {
+        recording.data.extend_from_slice(record.payload);
+        frames.push(Frame {
+            time: record.time.saturating_sub(start),
+            end: recording.data.len(),
+        });
+    }
+    recording.frames = Some(frames);
+    Ok(recording)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(sec: u32, usec: u32, payload: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&sec.to_le_bytes());
+        data.extend_from_slice(&usec.to_le_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn test_ttyrec() -> Result<()> {
+        let data = [
+            record(1_000_000_000, 500_000, b"hello"),
+            record(1_000_000_001, 0, b" world"),
+            record(1_000_000_003, 250_000, b"\r\n"),
+        ]
+        .concat();
+        assert!(detect(&data));
+        let recording = decode(&data)?;
+        assert_eq!(b"hello world\r\n", recording.data.as_slice());
+        assert_eq!(
+            Some(vec![
+                Frame {
+                    time: Duration::ZERO,
+                    end: 5
+                },
+                Frame {
+                    time: Duration::from_millis(500),
+                    end: 11
+                },
+                Frame {
+                    time: Duration::from_millis(2750),
+                    end: 13
+                },
+            ]),
+            recording.frames
+        );
+        assert!(!detect(b"\x1b[2J\x1b[Hhello world"));
+        assert!(decode(&data[..data.len() - 1]).is_err());
+        Ok(())
+    }
+}
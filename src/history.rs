@@ -0,0 +1,188 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+
+/// Name of the state file.
+const FILE_NAME: &str = "history.json";
+
+/// Play history and favorite state of an animation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Record {
+    /// Is the animation starred?
+    pub favorite: bool,
+    /// Number of times the animation is played.
+    pub play_count: u64,
+    /// Time when the animation is last played as milliseconds since the Unix epoch.
+    pub last_played: Option<u64>,
+}
+
+/// Favorites and play history that are persisted between sessions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    /// Records by animation, see [`Entry::history_key`].
+    ///
+    /// [`Entry::history_key`]: crate::library::Entry::history_key
+    #[serde(default)]
+    pub records: BTreeMap<String, Record>,
+    /// Path of the state file.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// Returns the default path of the state file.
+    ///
+    /// e.g. `~/.local/state/theattyr/history.json` on Linux.
+    pub fn path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join(env!("CARGO_PKG_NAME")).join(FILE_NAME))
+    }
+
+    /// Loads the history from the default path.
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the history from the given state file.
+    ///
+    /// The history is empty if the file does not exist yet. A corrupt file is moved
+    /// to a backup (e.g. `history.json.bak`) so that it is not saved over.
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let mut history = match fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(history) => history,
+                Err(e) => {
+                    let backup = path.with_extension("json.bak");
+                    return Err(match fs::rename(&path, &backup) {
+                        Ok(()) => eyre!(
+                            "invalid history in {}, moved to {}: {e}",
+                            path.display(),
+                            backup.display()
+                        ),
+                        Err(_) => eyre!("invalid history in {}: {e}", path.display()),
+                    });
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(eyre!("failed to read {}: {e}", path.display())),
+        };
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    /// Saves the history to the state file.
+    ///
+    /// The file is written via a temporary file that is renamed over it,
+    /// so that it is not left truncated if the write is interrupted.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let write = |path: &Path| -> io::Result<()> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+            fs::write(&temp_path, serde_json::to_string_pretty(self)?)
+                .and_then(|_| fs::rename(&temp_path, path))
+                .inspect_err(|_| {
+                    let _ = fs::remove_file(&temp_path);
+                })
+        };
+        write(path).map_err(|e| eyre!("failed to write {}: {e}", path.display()))
+    }
+
+    /// Returns the record of the given animation.
+    pub fn get(&self, key: &str) -> Option<&Record> {
+        self.records.get(key)
+    }
+
+    /// Returns whether the given animation is starred.
+    pub fn is_favorite(&self, key: &str) -> bool {
+        self.get(key).is_some_and(|record| record.favorite)
+    }
+
+    /// Stars or unstars the given animation and returns whether it is starred.
+    pub fn toggle_favorite(&mut self, key: &str) -> bool {
+        let record = self.records.entry(key.to_string()).or_default();
+        record.favorite = !record.favorite;
+        record.favorite
+    }
+
+    /// Records that the given animation is played now.
+    pub fn record_play(&mut self, key: &str) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+        let record = self.records.entry(key.to_string()).or_default();
+        record.play_count += 1;
+        record.last_played = Some(now);
+    }
+
+    /// Returns the given number of the last played animations, the most recent first.
+    pub fn recent(&self, len: usize) -> Vec<&str> {
+        let mut played = self
+            .records
+            .iter()
+            .filter_map(|(key, record)| record.last_played.map(|time| (time, key.as_str())))
+            .collect::<Vec<_>>();
+        played.sort_by_key(|(time, _)| Reverse(*time));
+        played.into_iter().take(len).map(|(_, key)| key).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() -> Result<()> {
+        let path = std::env::temp_dir()
+            .join(format!("theattyr-{}", std::process::id()))
+            .join(FILE_NAME);
+        let mut history = History::load_from(path.clone())?;
+        assert!(history.records.is_empty());
+        history.record_play("globe.vt");
+        history.record_play("globe.vt");
+        history.record_play("xmas.vt");
+        history
+            .records
+            .entry(String::from("xmas.vt"))
+            .or_default()
+            .last_played = Some(0);
+        assert!(history.toggle_favorite("xmas.vt"));
+        assert!(history.is_favorite("xmas.vt"));
+        assert!(!history.is_favorite("globe.vt"));
+        assert_eq!(vec!["globe.vt", "xmas.vt"], history.recent(5));
+        assert_eq!(vec!["globe.vt"], history.recent(1));
+        history.save()?;
+        let loaded = History::load_from(path.clone());
+        fs::write(&path, "{\"records\": {")?;
+        let corrupt = History::load_from(path.clone());
+        let backup = fs::read_to_string(path.with_extension("json.bak"));
+        let is_moved = !path.exists();
+        fs::remove_dir_all(path.parent().unwrap_or(&path))?;
+        assert_eq!(history, loaded?);
+        assert!(corrupt.is_err());
+        assert_eq!("{\"records\": {", backup?);
+        assert!(is_moved);
+        assert_eq!(
+            Some(2),
+            history.get("globe.vt").map(|record| record.play_count)
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,19 @@
 pub mod animation;
 pub mod app;
 pub mod args;
+pub mod config;
 pub mod event;
+pub mod export;
+pub mod format;
 pub mod fps;
+pub mod history;
+pub mod library;
+pub mod listing;
+pub mod manifest;
+pub mod pacing;
+pub mod playlist;
+pub mod raw;
+pub mod search;
+pub mod stream;
 
 pub use args::{Args, Parser};
@@ -0,0 +1,258 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{animation::Animations, manifest, stream::Stream};
+
+/// Name of the entry that is read from the standard input.
+const STDIN: &str = "<stdin>";
+
+/// Category of the animations that are not embedded.
+pub const LOCAL_CATEGORY: &str = "Local";
+
+/// Source of an animation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// Embedded into the binary.
+    Embedded,
+    /// Local file.
+    File(PathBuf),
+    /// Named pipe.
+    Pipe(PathBuf, Stream),
+    /// Standard input.
+    Stdin(Stream),
+}
+
+/// Entry in the animation library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Name of the animation.
+    pub name: String,
+    /// Source of the animation.
+    pub source: Source,
+}
+
+impl Entry {
+    /// Constructs a new embedded [`Entry`].
+    pub fn embedded(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            source: Source::Embedded,
+        }
+    }
+
+    /// Constructs a new local [`Entry`] with the given name.
+    pub fn file(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            source: Source::File(path.into()),
+        }
+    }
+
+    /// Constructs a new [`Entry`] for the standard input.
+    pub fn stdin() -> Self {
+        Self {
+            name: STDIN.to_string(),
+            source: Source::Stdin(Stream::new(std::io::stdin())),
+        }
+    }
+
+    /// Returns whether the animation is not embedded.
+    pub fn is_local(&self) -> bool {
+        self.source != Source::Embedded
+    }
+
+    /// Returns the stream of the animation if it is read from a stream.
+    pub fn stream(&self) -> Option<&Stream> {
+        match &self.source {
+            Source::Pipe(_, stream) | Source::Stdin(stream) => Some(stream),
+            _ => None,
+        }
+    }
+
+    /// Returns the metadata of the animation if it is embedded.
+    pub fn metadata(&self) -> Option<&'static manifest::Metadata> {
+        match self.source {
+            Source::Embedded => manifest::get(&self.name),
+            _ => None,
+        }
+    }
+
+    /// Returns the category of the animation.
+    pub fn category(&self) -> &str {
+        self.metadata()
+            .map(|metadata| metadata.category.as_str())
+            .unwrap_or(LOCAL_CATEGORY)
+    }
+
+    /// Returns the tags of the animation.
+    pub fn tags(&self) -> &[String] {
+        self.metadata()
+            .map(|metadata| metadata.tags.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the animation has the given tag (case-insensitive).
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Returns whether the animation is suitable for the safe mode.
+    ///
+    /// The animations that are not embedded are not rated.
+    pub fn is_safe(&self) -> bool {
+        self.metadata()
+            .is_none_or(|metadata| metadata.rating != manifest::Rating::Mature)
+    }
+
+    /// Returns the key of the animation in the play history.
+    ///
+    /// Streams are not recorded.
+    pub fn history_key(&self) -> Option<String> {
+        match &self.source {
+            Source::Embedded => Some(self.name.clone()),
+            Source::File(path) => Some(path.display().to_string()),
+            Source::Pipe(..) | Source::Stdin(_) => None,
+        }
+    }
+
+    /// Returns the description of the animation.
+    pub fn description(&self) -> Option<String> {
+        match &self.source {
+            Source::Embedded => self.metadata().map(|v| v.to_string()),
+            Source::File(path) | Source::Pipe(path, _) => Some(path.display().to_string()),
+            Source::Stdin(_) => Some(String::from("Standard Input")),
+        }
+    }
+
+    /// Returns whether the name or the description contains the given text (case-insensitive).
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.name.to_lowercase().contains(&text)
+            || self
+                .description()
+                .is_some_and(|v| v.to_lowercase().contains(&text))
+    }
+
+    /// Returns whether the entry refers to the given file name or path.
+    pub fn refers_to(&self, file: &str) -> bool {
+        match &self.source {
+            Source::Embedded => self.name == file,
+            Source::File(path) | Source::Pipe(path, _) => {
+                fs::canonicalize(file).is_ok_and(|p| &p == path)
+            }
+            Source::Stdin(_) => file == "-",
+        }
+    }
+
+    /// Loads the contents of the animation.
+    ///
+    /// Streams can not be loaded at once, see [`Entry::stream`].
+    pub fn load(&self) -> Result<Vec<u8>> {
+        match &self.source {
+            Source::Embedded => Animations::get(&self.name)
+                .map(|file| file.data.into_owned())
+                .ok_or_else(|| eyre!("no animation found: {}", self.name)),
+            Source::File(path) => {
+                fs::read(path).map_err(|e| eyre!("failed to read {}: {e}", path.display()))
+            }
+            Source::Pipe(..) | Source::Stdin(_) => Err(eyre!("{} is a stream", self.name)),
+        }
+    }
+}
+
+/// Returns the embedded animations merged with the given local file and directories.
+///
+/// Directories are scanned recursively. The file is read from the standard input if it is `-`.
+pub fn entries(file: Option<&str>, dirs: &[PathBuf]) -> Result<Vec<Entry>> {
+    let mut entries = Animations::iter().map(Entry::embedded).collect::<Vec<_>>();
+    let mut local = Vec::new();
+    match file.map(PathBuf::from) {
+        Some(file) if file.as_os_str() == "-" => entries.push(Entry::stdin()),
+        Some(file) if file.exists() => {
+            let name = file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| file.display().to_string());
+            local.push((name, file));
+        }
+        _ => {}
+    }
+    for dir in dirs {
+        let mut files = Vec::new();
+        collect_files(dir, &mut files)
+            .map_err(|e| eyre!("failed to read {}: {e}", dir.display()))?;
+        local.extend(files.into_iter().map(|file| {
+            let name = file
+                .strip_prefix(dir)
+                .unwrap_or(&file)
+                .display()
+                .to_string();
+            (name, file)
+        }));
+    }
+    let mut seen = HashSet::new();
+    for (name, path) in local {
+        let path =
+            fs::canonicalize(&path).map_err(|e| eyre!("failed to read {}: {e}", path.display()))?;
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        if is_fifo(&path) {
+            entries.push(Entry {
+                name,
+                source: Source::Pipe(path.clone(), Stream::from_path(path)),
+            });
+        } else {
+            entries.push(Entry::file(name, path));
+        }
+    }
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    Ok(entries)
+}
+
+/// Returns the entry that refers to the given file name or path.
+///
+/// See [`entries`] for the lookup of the file.
+pub fn find(file: &str, dirs: &[PathBuf]) -> Result<Entry> {
+    entries(Some(file), dirs)?
+        .into_iter()
+        .find(|entry| entry.refers_to(file))
+        .ok_or_else(|| eyre!("unknown file: {file}"))
+}
+
+/// Collects the files in the given directory recursively.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if !path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether the given path is a named pipe.
+#[cfg(unix)]
+fn is_fifo(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_fifo())
+}
+
+/// Returns whether the given path is a named pipe.
+#[cfg(not(unix))]
+fn is_fifo(_: &Path) -> bool {
+    false
+}
@@ -0,0 +1,323 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+use clap::ValueEnum;
+
+use crate::{
+    history::History,
+    library::{Entry, LOCAL_CATEGORY},
+    search::Match,
+};
+
+/// Category of the recently played animations.
+pub const RECENT_CATEGORY: &str = "Recently played";
+
+/// Number of the recently played animations that are listed.
+pub const RECENT_LEN: usize = 5;
+
+/// Sort order of the animations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortMode {
+    /// By name.
+    #[default]
+    Name,
+    /// Starred animations first.
+    Favorites,
+    /// Most played animations first.
+    Played,
+    /// Recently played animations first.
+    Recent,
+}
+
+impl SortMode {
+    /// Returns the next sort mode in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Favorites,
+            Self::Favorites => Self::Played,
+            Self::Played => Self::Recent,
+            Self::Recent => Self::Name,
+        }
+    }
+
+    /// Sorts the given matches based on the history.
+    ///
+    /// The sort is stable, i.e. the animations are ordered by name otherwise.
+    pub fn sort(self, matches: &mut [Match], entries: &[Entry], history: &History) {
+        let record = |m: &Match| {
+            entries[m.index]
+                .history_key()
+                .and_then(|key| history.get(&key).cloned())
+                .unwrap_or_default()
+        };
+        match self {
+            Self::Name => {}
+            Self::Favorites => matches.sort_by_cached_key(|m| !record(m).favorite),
+            Self::Played => matches.sort_by_cached_key(|m| Reverse(record(m).play_count)),
+            Self::Recent => matches.sort_by_cached_key(|m| Reverse(record(m).last_played)),
+        }
+    }
+}
+
+impl std::fmt::Display for SortMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Row of the animation list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Item {
+    /// Header of a category.
+    Category {
+        /// Name of the category.
+        name: String,
+        /// Number of the animations in the category.
+        len: usize,
+        /// Is the category collapsed?
+        is_collapsed: bool,
+    },
+    /// Animation at the given position in the matches.
+    Entry(usize),
+}
+
+/// Animation list that is optionally grouped by category.
+#[derive(Clone, Debug, Default)]
+pub struct Listing {
+    /// Listed animations in order.
+    pub matches: Vec<Match>,
+    /// Rows of the list.
+    pub items: Vec<Item>,
+    /// Collapsed categories.
+    pub collapsed: HashSet<String>,
+    /// Are the animations grouped by category?
+    pub is_grouped: bool,
+    /// Indices of the recently played animations that are listed on top if grouped.
+    pub recent: Vec<usize>,
+}
+
+impl Listing {
+    /// Updates the listed animations.
+    ///
+    /// The matches are sorted by category (the local files last) if they are grouped.
+    pub fn update(&mut self, entries: &[Entry], mut matches: Vec<Match>, is_grouped: bool) {
+        if is_grouped {
+            matches.sort_by_cached_key(|m| category_key(entries[m.index].category()));
+        }
+        self.matches = matches;
+        self.is_grouped = is_grouped;
+        self.rebuild(entries);
+    }
+
+    /// Rebuilds the rows of the list.
+    fn rebuild(&mut self, entries: &[Entry]) {
+        self.items.clear();
+        if !self.is_grouped {
+            self.items.extend((0..self.matches.len()).map(Item::Entry));
+            return;
+        }
+        let recent = self
+            .recent
+            .iter()
+            .filter_map(|index| self.matches.iter().position(|m| m.index == *index))
+            .collect::<Vec<_>>();
+        if !recent.is_empty() {
+            let is_collapsed = self.collapsed.contains(RECENT_CATEGORY);
+            self.items.push(Item::Category {
+                name: RECENT_CATEGORY.to_string(),
+                len: recent.len(),
+                is_collapsed,
+            });
+            if !is_collapsed {
+                self.items.extend(recent.into_iter().map(Item::Entry));
+            }
+        }
+        let mut position = 0;
+        while position < self.matches.len() {
+            let name = entries[self.matches[position].index].category();
+            let len = self.matches[position..]
+                .iter()
+                .take_while(|m| entries[m.index].category() == name)
+                .count();
+            let is_collapsed = self.collapsed.contains(name);
+            self.items.push(Item::Category {
+                name: name.to_string(),
+                len,
+                is_collapsed,
+            });
+            if !is_collapsed {
+                self.items
+                    .extend((position..position + len).map(Item::Entry));
+            }
+            position += len;
+        }
+    }
+
+    /// Sets the recently played animations and rebuilds the rows.
+    pub fn set_recent(&mut self, entries: &[Entry], recent: Vec<usize>) {
+        self.recent = recent;
+        self.rebuild(entries);
+    }
+
+    /// Collapses or expands the given category.
+    pub fn toggle(&mut self, entries: &[Entry], category: &str) {
+        if !self.collapsed.remove(category) {
+            self.collapsed.insert(category.to_string());
+        }
+        self.rebuild(entries);
+    }
+
+    /// Collapses all the categories or expands them if they are all collapsed.
+    pub fn toggle_all(&mut self, entries: &[Entry]) {
+        let categories = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Category { name, .. } => Some(name.clone()),
+                Item::Entry(_) => None,
+            })
+            .collect::<HashSet<_>>();
+        if categories.is_subset(&self.collapsed) {
+            self.collapsed.clear();
+        } else {
+            self.collapsed.extend(categories);
+        }
+        self.rebuild(entries);
+    }
+
+    /// Returns the row of the given animation, expanding its category if needed.
+    ///
+    /// The row in its category is preferred over the recently played ones.
+    pub fn reveal(&mut self, entries: &[Entry], index: usize) -> Option<usize> {
+        let position = self.matches.iter().position(|m| m.index == index)?;
+        if self.is_grouped && self.collapsed.remove(entries[index].category()) {
+            self.rebuild(entries);
+        }
+        self.items
+            .iter()
+            .rposition(|item| *item == Item::Entry(position))
+    }
+
+    /// Returns the row of the first animation.
+    pub fn first_entry(&self) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| matches!(item, Item::Entry(_)))
+    }
+
+    /// Returns the row of the given category.
+    pub fn category_row(&self, category: &str) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| matches!(item, Item::Category { name, .. } if name == category))
+    }
+}
+
+/// Returns the sort key of the given category.
+fn category_key(category: &str) -> (bool, String) {
+    (category == LOCAL_CATEGORY, category.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing() {
+        let entries = vec![
+            Entry::file("art.vt", "/tmp/art.vt"),
+            Entry::embedded("castle.vt"),
+            Entry::embedded("trek.vt"),
+            Entry::embedded("xmas.vt"),
+            Entry::embedded("skyway.vt"),
+        ];
+        let matches = (0..entries.len())
+            .map(|index| Match {
+                index,
+                ..Match::default()
+            })
+            .collect::<Vec<_>>();
+        let mut listing = Listing::default();
+        listing.update(&entries, matches.clone(), false);
+        assert_eq!((0..5).map(Item::Entry).collect::<Vec<_>>(), listing.items);
+        listing.update(&entries, matches, true);
+        assert_eq!(
+            vec![1, 4, 3, 2, 0],
+            listing.matches.iter().map(|m| m.index).collect::<Vec<_>>()
+        );
+        let category = |name: &str, len, is_collapsed| Item::Category {
+            name: name.to_string(),
+            len,
+            is_collapsed,
+        };
+        assert_eq!(
+            vec![
+                category("Disneyland", 2, false),
+                Item::Entry(0),
+                Item::Entry(1),
+                category("Holidays", 1, false),
+                Item::Entry(2),
+                category("Star Trek", 1, false),
+                Item::Entry(3),
+                category(LOCAL_CATEGORY, 1, false),
+                Item::Entry(4),
+            ],
+            listing.items
+        );
+        listing.toggle(&entries, "Disneyland");
+        assert_eq!(category("Disneyland", 2, true), listing.items[0]);
+        assert_eq!(Some(3), listing.category_row("Star Trek"));
+        assert_eq!(Some(2), listing.first_entry());
+        assert_eq!(Some(2), listing.reveal(&entries, 4));
+        assert_eq!(9, listing.items.len());
+        listing.toggle_all(&entries);
+        assert_eq!(4, listing.items.len());
+        listing.toggle_all(&entries);
+        assert_eq!(9, listing.items.len());
+        listing.set_recent(&entries, vec![4]);
+        assert_eq!(
+            vec![category(RECENT_CATEGORY, 1, false), Item::Entry(1)],
+            listing.items[..2]
+        );
+        assert_eq!(Some(4), listing.reveal(&entries, 4));
+    }
+
+    #[test]
+    fn test_sort() {
+        let entries = ["bambi.vt", "globe.vt", "xmas.vt"]
+            .into_iter()
+            .map(Entry::embedded)
+            .collect::<Vec<_>>();
+        let mut history = History::default();
+        history.record_play("xmas.vt");
+        history.record_play("globe.vt");
+        history.record_play("globe.vt");
+        history.toggle_favorite("xmas.vt");
+        history
+            .records
+            .entry(String::from("xmas.vt"))
+            .or_default()
+            .last_played = Some(u64::MAX);
+        for (mode, expected) in [
+            (SortMode::Name, vec![0, 1, 2]),
+            (SortMode::Favorites, vec![2, 0, 1]),
+            (SortMode::Played, vec![1, 2, 0]),
+            (SortMode::Recent, vec![2, 1, 0]),
+        ] {
+            let mut matches = (0..entries.len())
+                .map(|index| Match {
+                    index,
+                    ..Match::default()
+                })
+                .collect::<Vec<_>>();
+            mode.sort(&mut matches, &entries, &history);
+            assert_eq!(
+                expected,
+                matches.iter().map(|m| m.index).collect::<Vec<_>>(),
+                "{mode}"
+            );
+        }
+    }
+}
@@ -1,11 +1,39 @@
+use ratatui::crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+};
+use std::io;
 use theattyr::*;
 
 fn main() -> color_eyre::Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
     color_eyre::install()?;
+    args.merge(&config::Config::load(args.config.as_deref())?);
+    match args.command.take() {
+        Some(args::Command::Play { file }) => args.file = Some(file),
+        Some(args::Command::Export {
+            file,
+            format,
+            at,
+            output,
+        }) => return export::export(&args, &file, format, at, output.as_deref()),
+        Some(args::Command::Snapshot { file, format }) => {
+            return export::snapshot(&args, &file, format)
+        }
+        None => {}
+    }
+    if args.raw {
+        let file = args
+            .file
+            .as_deref()
+            .ok_or_else(|| color_eyre::eyre::eyre!("a file is required for the raw mode"))?;
+        return raw::play(&args, file);
+    }
     let terminal = ratatui::init();
+    execute!(io::stdout(), EnableMouseCapture)?;
     let event_handler = event::EventHandler::new(args.tick_rate);
-    let result = app::App::new(event_handler, args).run(terminal);
+    let result = app::App::new(event_handler, args).and_then(|app| app.run(terminal));
+    execute!(io::stdout(), DisableMouseCapture)?;
     ratatui::restore();
     result
 }
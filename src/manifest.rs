@@ -0,0 +1,144 @@
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+use serde::Deserialize;
+
+/// Contents of the manifest, see [`manifest`].
+const MANIFEST: &str = include_str!("../manifest.toml");
+
+/// Content rating.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rating {
+    /// Suitable for everyone.
+    #[default]
+    General,
+    /// Not suitable for shared screens, hidden in the safe mode.
+    Mature,
+}
+
+/// Metadata of an embedded animation.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Metadata {
+    /// Title.
+    pub title: String,
+    /// Category in the list.
+    pub category: String,
+    /// Author.
+    pub author: Option<String>,
+    /// Year of creation.
+    pub year: Option<u16>,
+    /// URL of the original source.
+    pub source: Option<String>,
+    /// Tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Content warning.
+    pub warning: Option<String>,
+    /// Content rating.
+    #[serde(default)]
+    pub rating: Rating,
+    /// Original terminal size as rows and columns.
+    pub size: Option<(u16, u16)>,
+    /// Recommended playback speed multiplier.
+    pub speed: Option<f64>,
+}
+
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title)?;
+        if let Some(warning) = &self.warning {
+            write!(f, " [{warning}]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Manifest of the embedded animations.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    /// Metadata of the files by name.
+    files: HashMap<String, Metadata>,
+}
+
+/// Returns the metadata of the embedded animations by file name.
+///
+/// The manifest is parsed once on the first call.
+pub fn manifest() -> &'static HashMap<String, Metadata> {
+    static MEM: OnceLock<HashMap<String, Metadata>> = OnceLock::new();
+    MEM.get_or_init(|| {
+        toml::from_str::<Manifest>(MANIFEST)
+            .expect("invalid manifest")
+            .files
+    })
+}
+
+/// Returns the metadata of the given embedded animation.
+pub fn get(name: &str) -> Option<&'static Metadata> {
+    manifest().get(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Animations, MAX_SPEED, MIN_SPEED};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_manifest() {
+        let files = Animations::iter()
+            .map(|name| name.to_string())
+            .collect::<HashSet<_>>();
+        let entries = manifest().keys().cloned().collect::<HashSet<_>>();
+        assert!(
+            files.difference(&entries).next().is_none(),
+            "missing from the manifest: {:?}",
+            files.difference(&entries).collect::<Vec<_>>()
+        );
+        assert!(
+            entries.difference(&files).next().is_none(),
+            "unknown files in the manifest: {:?}",
+            entries.difference(&files).collect::<Vec<_>>()
+        );
+        for (name, metadata) in manifest() {
+            assert!(
+                metadata.rating == Rating::General || metadata.warning.is_some(),
+                "{name}"
+            );
+            assert!(!metadata.title.is_empty(), "{name}");
+            assert!(!metadata.category.is_empty(), "{name}");
+            assert!(
+                metadata
+                    .speed
+                    .is_none_or(|speed| (MIN_SPEED..=MAX_SPEED).contains(&speed)),
+                "{name}"
+            );
+            assert!(
+                metadata
+                    .size
+                    .is_none_or(|(rows, cols)| rows > 0 && cols > 0),
+                "{name}"
+            );
+        }
+        assert_eq!(
+            Some(&Metadata {
+                title: String::from(
+                    "VT100 FONT: The VT-100 Torture Test by Joe Smith (May 8, 1985)"
+                ),
+                category: String::from("Test patterns"),
+                author: Some(String::from("Joe Smith")),
+                year: Some(1985),
+                source: Some(String::from(
+                    "http://artscene.textfiles.com/vt100/torturet.vt"
+                )),
+                tags: vec![String::from("test")],
+                warning: None,
+                rating: Rating::General,
+                size: Some((24, 80)),
+                speed: None,
+            }),
+            get("torturet.vt")
+        );
+    }
+}
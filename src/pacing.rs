@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+/// Number of bits sent per byte on a serial line (8N1: start bit, 8 data bits, stop bit).
+const BITS_PER_BYTE: f64 = 10.;
+
+/// Playback pacing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pacing {
+    /// Release a line per interval.
+    Line(Duration),
+    /// Release bytes at the rate of a serial line with the given baud rate.
+    Baud(u32),
+}
+
+/// Maps the playback time to offsets in the animation data.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Timeline {
+    /// Bytes are released at a constant rate.
+    Rate {
+        /// Bytes per second.
+        bytes_per_sec: f64,
+    },
+    /// Bytes are released in frames.
+    Frames(Vec<Frame>),
+}
+
+/// A chunk of data released at once.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame {
+    /// Time of the frame since the start of the playback.
+    pub time: Duration,
+    /// Offset where the frame ends in the data.
+    pub end: usize,
+}
+
+impl Timeline {
+    /// Constructs a new instance of [`Timeline`] for the given data.
+    pub fn new(pacing: Pacing, data: &[u8]) -> Self {
+        let mut timeline = match pacing {
+            Pacing::Line(_) => Self::Frames(Vec::new()),
+            Pacing::Baud(rate) => Self::Rate {
+                bytes_per_sec: rate as f64 / BITS_PER_BYTE,
+            },
+        };
+        timeline.extend(pacing, data);
+        timeline
+    }
+
+    /// Extends the timeline with the data that is appended since the last update.
+    pub fn extend(&mut self, pacing: Pacing, data: &[u8]) {
+        let (Self::Frames(frames), Pacing::Line(interval)) = (self, pacing) else {
+            return;
+        };
+        // the last line might be continued
+        if frames
+            .last()
+            .is_some_and(|frame| data[frame.end - 1] != b'\n')
+        {
+            frames.pop();
+        }
+        let start = frames.last().map(|frame| frame.end).unwrap_or_default();
+        let mut ends = data[start..]
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'\n')
+            .map(|(i, _)| start + i + 1)
+            .collect::<Vec<_>>();
+        if ends.last().copied().unwrap_or(start) != data.len() {
+            ends.push(data.len());
+        }
+        for end in ends {
+            frames.push(Frame {
+                time: interval * frames.len() as u32,
+                end,
+            });
+        }
+    }
+
+    /// Returns the offset in the data that is reached at the given time.
+    pub fn offset_at(&self, time: Duration) -> usize {
+        match self {
+            Self::Rate { bytes_per_sec } => (time.as_secs_f64() * bytes_per_sec) as usize,
+            Self::Frames(frames) => {
+                let index = frames.partition_point(|frame| frame.time <= time);
+                index.checked_sub(1).map(|i| frames[i].end).unwrap_or(0)
+            }
+        }
+    }
+
+    /// Returns the frames for data of the given length.
+    ///
+    /// Constant rates are sampled at the given interval.
+    pub fn frames(&self, len: usize, interval: Duration) -> Vec<Frame> {
+        let Self::Frames(frames) = self else {
+            let mut frames = Vec::new();
+            let (mut time, mut end) = (Duration::ZERO, 0);
+            while end < len && !interval.is_zero() {
+                time += interval;
+                let offset = self.offset_at(time).min(len);
+                if offset > end {
+                    end = offset;
+                    frames.push(Frame { time, end });
+                }
+            }
+            return frames;
+        };
+        frames.clone()
+    }
+
+    /// Returns the time at which the given offset in the data is reached.
+    pub fn time_at(&self, offset: usize) -> Duration {
+        match self {
+            Self::Rate { bytes_per_sec } => Duration::from_secs_f64(offset as f64 / bytes_per_sec),
+            Self::Frames(frames) => {
+                let index = frames.partition_point(|frame| frame.end < offset);
+                frames
+                    .get(index)
+                    .or(frames.last())
+                    .map(|frame| frame.time)
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_timeline() {
+        let interval = Duration::from_millis(100);
+        let timeline = Timeline::new(Pacing::Line(interval), b"ab\ncd\nef");
+        assert_eq!(3, timeline.offset_at(Duration::ZERO));
+        assert_eq!(3, timeline.offset_at(Duration::from_millis(99)));
+        assert_eq!(6, timeline.offset_at(interval));
+        assert_eq!(8, timeline.offset_at(interval * 2));
+        assert_eq!(8, timeline.offset_at(interval * 10));
+        assert_eq!(Duration::ZERO, timeline.time_at(0));
+        assert_eq!(interval, timeline.time_at(4));
+        assert_eq!(interval, timeline.time_at(6));
+        assert_eq!(interval * 2, timeline.time_at(7));
+    }
+
+    #[test]
+    fn test_extend_timeline() {
+        let interval = Duration::from_millis(100);
+        let pacing = Pacing::Line(interval);
+        let mut timeline = Timeline::new(pacing, b"ab\nc");
+        timeline.extend(pacing, b"ab\ncd\nef\n");
+        assert_eq!(Timeline::new(pacing, b"ab\ncd\nef\n"), timeline);
+        assert_eq!(Timeline::Frames(Vec::new()), Timeline::new(pacing, b""));
+    }
+
+    #[test]
+    fn test_baud_timeline() {
+        let timeline = Timeline::new(Pacing::Baud(9600), b"");
+        assert_eq!(0, timeline.offset_at(Duration::ZERO));
+        assert_eq!(960, timeline.offset_at(Duration::from_secs(1)));
+        assert_eq!(120, timeline.offset_at(Duration::from_millis(1000 / 8)));
+        assert_eq!(Duration::from_secs(1), timeline.time_at(960));
+        assert_eq!(
+            vec![
+                Frame {
+                    time: Duration::from_millis(500),
+                    end: 480
+                },
+                Frame {
+                    time: Duration::from_secs(1),
+                    end: 500
+                },
+            ],
+            timeline.frames(500, Duration::from_millis(500))
+        );
+    }
+}
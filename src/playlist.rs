@@ -0,0 +1,96 @@
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
+
+/// Play mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PlayMode {
+    /// Stop at the end of the animation.
+    #[default]
+    Once,
+    /// Repeat the current animation.
+    Repeat,
+    /// Advance to the next animation in the list.
+    Advance,
+    /// Play the animations in random order.
+    Shuffle,
+}
+
+impl PlayMode {
+    /// Returns the next play mode in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Once => Self::Repeat,
+            Self::Repeat => Self::Advance,
+            Self::Advance => Self::Shuffle,
+            Self::Shuffle => Self::Once,
+        }
+    }
+}
+
+impl std::fmt::Display for PlayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Playlist that decides what to play next.
+#[derive(Clone, Debug, Default)]
+pub struct Playlist {
+    /// Play mode.
+    pub mode: PlayMode,
+    /// Animations that are left to play in shuffle mode.
+    queue: Vec<usize>,
+}
+
+impl Playlist {
+    /// Constructs a new instance of [`Playlist`].
+    pub fn new(mode: PlayMode) -> Self {
+        Self {
+            mode,
+            queue: Vec::new(),
+        }
+    }
+
+    /// Returns the index of the animation to play after the current one.
+    ///
+    /// In shuffle mode, every animation is played once before the order is reshuffled.
+    pub fn next(&mut self, current: usize, len: usize) -> Option<usize> {
+        match self.mode {
+            PlayMode::Once => None,
+            PlayMode::Repeat => Some(current),
+            PlayMode::Advance => Some((current + 1) % len.max(1)),
+            PlayMode::Shuffle => {
+                self.queue.retain(|i| *i < len && *i != current);
+                if self.queue.is_empty() {
+                    self.queue = (0..len).filter(|i| *i != current).collect();
+                    self.queue.shuffle(&mut rand::thread_rng());
+                }
+                self.queue.pop().or(Some(current))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playlist() {
+        assert_eq!(None, Playlist::new(PlayMode::Once).next(1, 3));
+        assert_eq!(Some(1), Playlist::new(PlayMode::Repeat).next(1, 3));
+        assert_eq!(Some(0), Playlist::new(PlayMode::Advance).next(2, 3));
+        let mut playlist = Playlist::new(PlayMode::Shuffle);
+        let mut current = 0;
+        let mut played = vec![current];
+        for _ in 0..4 {
+            current = playlist.next(current, 5).expect("no animation");
+            played.push(current);
+        }
+        played.sort();
+        assert_eq!(vec![0, 1, 2, 3, 4], played);
+    }
+}
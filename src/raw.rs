@@ -0,0 +1,120 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use color_eyre::{eyre::eyre, Result};
+use ratatui::crossterm::terminal;
+use vt100::{MouseProtocolEncoding, MouseProtocolMode, Screen};
+
+use crate::{animation::Animation, format, library, Args};
+
+/// Escape sequences that restore the terminal state that the animations might change.
+///
+/// Resets the attributes, character sets, scrolling region, origin mode, line wrapping,
+/// keypad modes and shows the cursor while the screen is left as is.
+const RESET: &[u8] = b"\x1b[0m\x1b(B\x1b)B\x0f\x1b[r\x1b[?6l\x1b[?7h\x1b[?1l\x1b>\x1b[?25h\r\n";
+
+/// Returns the escape sequences that restore the terminal state after the given screen.
+///
+/// The alternate screen, mouse reporting and bracketed paste are only reset if they are
+/// enabled, since leaving the alternate screen also restores the saved cursor.
+fn reset(screen: &Screen) -> Vec<u8> {
+    let mut reset = Vec::new();
+    if screen.alternate_screen() {
+        reset.extend_from_slice(b"\x1b[?1049l");
+    }
+    if screen.mouse_protocol_mode() != MouseProtocolMode::None {
+        reset.extend_from_slice(b"\x1b[?1000l\x1b[?1002l\x1b[?1003l");
+    }
+    if screen.mouse_protocol_encoding() != MouseProtocolEncoding::Default {
+        reset.extend_from_slice(b"\x1b[?1005l\x1b[?1006l");
+    }
+    if screen.bracketed_paste() {
+        reset.extend_from_slice(b"\x1b[?2004l");
+    }
+    reset.extend_from_slice(RESET);
+    reset
+}
+
+/// Plays the given file directly on the terminal without the user interface.
+///
+/// The paced output is written as is, like `cat` would do. The terminal state is
+/// restored at the end or when the playback is interrupted via Ctrl-C, unless the
+/// output is not a terminal.
+pub fn play(args: &Args, file: &str) -> Result<()> {
+    let entry = library::find(file, &args.dir)?;
+    let (cols, rows) = terminal::size()
+        .ok()
+        .filter(|(cols, rows)| *cols > 0 && *rows > 0)
+        .unwrap_or((80, 24));
+    let mut animation = match entry.stream() {
+        Some(stream) => Animation::from_stream(stream.clone(), args.pacing(), rows, cols),
+        None => Animation::from_recording(
+            format::decode_with_timing(
+                &entry.name,
+                entry.load()?,
+                args.timing.as_deref(),
+                args.encoding,
+            )?,
+            args.pacing(),
+            rows,
+            cols,
+        ),
+    };
+    animation.set_speed(args.playback_speed(&entry));
+    let is_interrupted = Arc::new(AtomicBool::new(false));
+    let handler = Arc::clone(&is_interrupted);
+    ctrlc::set_handler(move || handler.store(true, Ordering::Release))
+        .map_err(|e| eyre!("failed to set the Ctrl-C handler: {e}"))?;
+    let mut stdout = io::stdout().lock();
+    let mut position = 0;
+    let result = loop {
+        if is_interrupted.load(Ordering::Acquire) {
+            break Ok(());
+        }
+        animation.update();
+        if let Err(e) = stdout
+            .write_all(&animation.data[position..animation.position])
+            .and_then(|_| stdout.flush())
+        {
+            break Err(e);
+        }
+        position = animation.position;
+        if animation.is_rendered {
+            break Ok(());
+        }
+        thread::sleep(args.frame_interval());
+    };
+    // e.g. redirected to a file
+    if stdout.is_terminal() {
+        stdout.write_all(&reset(animation.parser.screen()))?;
+        stdout.flush()?;
+    }
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vt100::Parser;
+
+    #[test]
+    fn test_reset() {
+        let mut parser = Parser::new(24, 80, 0);
+        assert_eq!(RESET, reset(parser.screen()));
+        parser.process(b"\x1b[?1049h\x1b[?1002h\x1b[?1006h\x1b[?2004hbeer");
+        assert_eq!(
+            [
+                b"\x1b[?1049l\x1b[?1000l\x1b[?1002l\x1b[?1003l\x1b[?1005l\x1b[?1006l\x1b[?2004l",
+                RESET
+            ]
+            .concat(),
+            reset(parser.screen())
+        );
+    }
+}
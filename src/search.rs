@@ -0,0 +1,150 @@
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use crate::library::Entry;
+
+/// Entry that matches the search query.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the entry.
+    pub index: usize,
+    /// Score of the match (higher is better).
+    pub score: i64,
+    /// Indices of the matched characters in the name.
+    pub indices: Vec<usize>,
+}
+
+/// Fuzzy search in the animation list.
+#[derive(Clone, Debug, Default)]
+pub struct Search {
+    /// Is the search prompt active?
+    pub is_active: bool,
+    /// Search query.
+    ///
+    /// Words that start with `#` filter by tag, e.g. `#christmas tree`.
+    pub query: String,
+}
+
+impl Search {
+    /// Returns the tags in the query.
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.query
+            .split_whitespace()
+            .filter_map(|word| word.strip_prefix('#'))
+            .filter(|tag| !tag.is_empty())
+    }
+
+    /// Returns the query without the tags.
+    fn text(&self) -> String {
+        self.query
+            .split_whitespace()
+            .filter(|word| !word.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns whether the query has text to fuzzy match, i.e. not only tags.
+    pub fn is_fuzzy(&self) -> bool {
+        !self.text().is_empty()
+    }
+
+    /// Returns the entries that match the query, the best match first.
+    ///
+    /// Both the names and the descriptions are matched. The entries that match the
+    /// tags are returned in the given order if there is no text to match.
+    pub fn filter(&self, entries: &[Entry]) -> Vec<Match> {
+        let entries = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.tags().all(|tag| entry.has_tag(tag)));
+        let query = self.text();
+        if query.is_empty() {
+            return entries
+                .map(|(index, _)| Match {
+                    index,
+                    ..Match::default()
+                })
+                .collect();
+        }
+        let query = query.as_str();
+        let matcher = SkimMatcherV2::default().smart_case();
+        let mut matches = entries
+            .filter_map(|(index, entry)| {
+                let name = matcher.fuzzy_indices(&entry.name, query);
+                let description = entry
+                    .description()
+                    .and_then(|description| matcher.fuzzy_match(&description, query));
+                match (name, description) {
+                    (Some((score, indices)), description) => Some(Match {
+                        index,
+                        score: score.max(description.unwrap_or_default()),
+                        indices,
+                    }),
+                    (None, Some(score)) => Some(Match {
+                        index,
+                        score,
+                        indices: Vec::new(),
+                    }),
+                    (None, None) => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| b.score.cmp(&a.score).then(a.index.cmp(&b.index)));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let entries = ["bambi.vt", "globe.vt", "movglobe.vt", "torturet.vt"]
+            .into_iter()
+            .map(Entry::embedded)
+            .collect::<Vec<_>>();
+        let mut search = Search::default();
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            search
+                .filter(&entries)
+                .iter()
+                .map(|m| m.index)
+                .collect::<Vec<_>>()
+        );
+        search.query = String::from("glb");
+        let matches = search.filter(&entries);
+        assert_eq!(
+            vec![1, 2],
+            matches.iter().map(|m| m.index).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0, 1, 3], matches[0].indices);
+        // matched by the description
+        search.query = String::from("VT-100 Torture");
+        let matches = search.filter(&entries);
+        assert_eq!(vec![3], matches.iter().map(|m| m.index).collect::<Vec<_>>());
+        assert!(matches[0].indices.is_empty());
+        search.query = String::from("xyz");
+        assert!(search.filter(&entries).is_empty());
+        search.query = String::from("#Earth");
+        assert!(!search.is_fuzzy());
+        assert_eq!(
+            vec![1, 2],
+            search
+                .filter(&entries)
+                .iter()
+                .map(|m| m.index)
+                .collect::<Vec<_>>()
+        );
+        search.query = String::from("#earth mov");
+        assert!(search.is_fuzzy());
+        assert_eq!(
+            vec![2],
+            search
+                .filter(&entries)
+                .iter()
+                .map(|m| m.index)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    mem,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// Size of the chunks read from the source.
+const CHUNK_SIZE: usize = 4096;
+
+/// Bytes received from the source.
+#[derive(Debug, Default)]
+struct Buffer {
+    /// Received bytes that are not taken by the consumer yet.
+    data: Vec<u8>,
+    /// Number of bytes taken by the consumer.
+    taken: usize,
+}
+
+/// Byte stream that is read from a source in the background.
+///
+/// The received bytes are moved to the consumer to keep a single copy of them,
+/// they are given back via [`Stream::restore`] so that the stream can be replayed.
+#[derive(Clone, Debug, Default)]
+pub struct Stream {
+    /// Bytes received so far.
+    buffer: Arc<Mutex<Buffer>>,
+    /// Is the end of the stream reached?
+    is_finished: Arc<AtomicBool>,
+}
+
+impl PartialEq for Stream {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.buffer, &other.buffer)
+    }
+}
+
+impl Eq for Stream {}
+
+impl Stream {
+    /// Constructs a new instance of [`Stream`] that reads from the given source.
+    pub fn new<R: Read + Send + 'static>(reader: R) -> Self {
+        Self::spawn(move || Ok(reader))
+    }
+
+    /// Constructs a new instance of [`Stream`] that reads from the given file.
+    ///
+    /// The file is opened in the background since opening a named pipe blocks until
+    /// there is a writer.
+    pub fn from_path(path: PathBuf) -> Self {
+        Self::spawn(move || File::open(path))
+    }
+
+    /// Spawns a thread that reads the source returned by the given function.
+    fn spawn<R: Read, F: FnOnce() -> io::Result<R> + Send + 'static>(open: F) -> Self {
+        let stream = Self::default();
+        let (buffer, is_finished) = (stream.buffer.clone(), stream.is_finished.clone());
+        thread::spawn(move || {
+            if let Ok(mut reader) = open() {
+                let mut chunk = [0; CHUNK_SIZE];
+                loop {
+                    match reader.read(&mut chunk) {
+                        Ok(0) => break,
+                        Ok(n) => match buffer.lock() {
+                            Ok(mut buffer) => buffer.data.extend_from_slice(&chunk[..n]),
+                            Err(_) => break,
+                        },
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        Err(_) => break,
+                    }
+                }
+            }
+            is_finished.store(true, Ordering::Release);
+        });
+        stream
+    }
+
+    /// Moves the bytes received after the length of the given buffer to it.
+    ///
+    /// Nothing is received if the buffer does not hold the bytes taken so far.
+    /// Returns `true` if the end of the stream is reached and all the bytes are received.
+    pub fn receive(&self, buffer: &mut Vec<u8>) -> bool {
+        let is_finished = self.is_finished.load(Ordering::Acquire);
+        if let Ok(mut state) = self.buffer.lock() {
+            if buffer.len() == state.taken {
+                state.taken += state.data.len();
+                if buffer.is_empty() {
+                    *buffer = mem::take(&mut state.data);
+                } else {
+                    buffer.append(&mut state.data);
+                }
+            }
+        }
+        is_finished
+    }
+
+    /// Gives back the bytes taken via [`Stream::receive`] so that the stream can be replayed.
+    pub fn restore(&self, mut data: Vec<u8>) {
+        if let Ok(mut state) = self.buffer.lock() {
+            if data.len() == state.taken {
+                data.append(&mut state.data);
+                state.data = data;
+                state.taken = 0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream() {
+        let stream = Stream::new(&b"hello world"[..]);
+        let mut buffer = Vec::new();
+        while !stream.receive(&mut buffer) {
+            thread::yield_now();
+        }
+        assert_eq!(b"hello world", buffer.as_slice());
+        let mut replay = Vec::new();
+        assert!(stream.clone().receive(&mut replay));
+        assert!(replay.is_empty());
+        stream.restore(buffer);
+        assert!(stream.clone().receive(&mut replay));
+        assert_eq!(b"hello world", replay.as_slice());
+    }
+}